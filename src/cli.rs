@@ -0,0 +1,720 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, Write},
+    net::TcpListener,
+    path::Path,
+};
+
+use crate::examples::{
+    self,
+    batch::run_batch,
+    compile_fail,
+    export::{self, Format},
+    guessing_game::{
+        self,
+        leaderboard::{self, leaderboard_path, GameResult, Leaderboard},
+        recorded_game,
+        recording::{self, Session},
+        server, words, Mode,
+    },
+    mini_grep,
+    progress::{self, default_learner, progress_path, Progress},
+    quiz::quiz,
+    script::Script,
+    search::{print_hits, search_sources},
+    Example,
+};
+
+const USAGE: &str = "\
+usage: rust-book-examples <command> [args...]
+
+commands:
+  examples   list and run the examples from the book
+  grep       search files with mini_grep, or build and query a word index
+  guess      play the guessing game
+  help       show the help for a command
+
+run `rust-book-examples <command> --help` for the help of a single command";
+
+const EXAMPLES_USAGE: &str = "\
+usage: rust-book-examples examples list
+       rust-book-examples examples run <name> [--user <name>] [--input <answer>]... [--input-file <path>]
+       rust-book-examples examples run --all [--user <name>] [--input <answer>]... [--input-file <path>]
+       rust-book-examples examples batch [--input <answer>]... [--input-file <path>]
+       rust-book-examples examples explain-error [<name>]
+       rust-book-examples examples export [--format md|html] <outdir> [--input <answer>]...
+       rust-book-examples examples progress [complete <name> | reset | export [<file>]] [--user <name>]
+       rust-book-examples examples quiz [<name>]... [--input <answer>]... [--input-file <path>]
+       rust-book-examples examples search <query>...
+
+list    print every example with its chapter and what it shows
+run     run one example by name, or every example in order with --all
+batch   run every example even when some panic or fail, then print how each one went,
+        examples that ask for input get the scripted answers and never read stdin
+explain-error   list the code from the examples that does not compile, or compile the
+                one called <name> with rustc and show the error next to the explanation
+export          write a study page for every example into <outdir>, with the source,
+                its comments as prose and what it prints, and an index by chapter
+                the format is md unless --format html is given
+progress        show which examples you have run and completed, chapter by chapter, and what is next,
+                `complete <name>` marks an example as done, `reset` forgets your progress
+                and `export` prints it as json, or writes it to <file>
+                every successful `run` is recorded, under --user or your login name
+                the file is $RUST_BOOK_PROGRESS, or progress.json in ~/.local/share/rust-book-examples
+quiz            show snippets from the examples, or only from the ones named, and ask what they print,
+                an answer ends with an empty line and is checked against what the code really prints
+search          find the lines of the example sources that contain <query>, with the module,
+                the line number and the comment heading the line is under, case matters,
+                the tools built around the examples, like the game server, are left out
+
+--input <answer>      answer the next question with <answer> instead of reading stdin,
+                      can be given more than once and a value with newlines is several answers
+--input-file <path>   read the answers from a file, one per line
+running out of scripted answers stops the example or game with an error
+with --all, batch and export every example gets the answers from the start";
+
+const GUESS_USAGE: &str = "\
+usage: rust-book-examples guess [options] [--input <answer>]... [--input-file <path>]
+       rust-book-examples guess leaderboard [easy|normal|hard]
+       rust-book-examples guess replay <log> [--steps]
+       rust-book-examples guess serve [--port <n>] [options]
+       rust-book-examples guess connect [<host>[:<port>]] [--name <player>] [--input <answer>]...
+
+play the guessing game from chapter 2
+the guesses are read from stdin, or taken from --input and --input-file like `examples run`
+every finished game goes on the leaderboard, `guess leaderboard` shows the best win of each player
+`guess serve` starts a game for everyone on the network, the first to find the secret wins,
+players join with `guess connect`, telnet or netcat, on port 7878 unless --port says otherwise
+
+options:
+  --difficulty <level>   easy picks from 1 to 10 (the default), normal from 1 to 100
+                         and hard from 1 to 1000
+  --range <start>..=<end>
+                         pick from a range of your own, 1..=50, 1..51 and 1-50 are the same
+  --attempts <n>         lose when n guesses were not enough, the secret is shown then
+  --seed <n>             pick the secret with a seeded generator, the same seed gives the same game
+  --name <player>        whose game it is on the leaderboard, your user name by default
+  --hints                say how hot or cold every wrong guess is
+  --record <log>         add the game to a log file, with the seed and every guess and when it came,
+                         only for the number game
+  --solver               watch the computer find its own secret number with binary search
+  --reverse              you think of the number and answer higher, lower or correct,
+                         while the computer guesses
+  --wordle               guess a word instead, every letter in the right place is shown as [a]
+                         and every letter that is in the word somewhere else as (a)
+  --hangman              guess a word one letter at a time, --attempts is the number of misses
+  --words <file>         draw the words from a text of your own instead of poem.txt
+
+winning shows a score, 100 for needing no more guesses than halving the range every time would
+instead of a guess you can type hint (costs a guess), history, giveup or quit
+`guess replay` shows how the games in a log went, with --steps one guess per press of enter";
+
+const COMMANDS: &[&str] = &["examples", "grep", "guess", "help"];
+
+fn help(command: &str) -> Option<String> {
+    match command {
+        "examples" => Some(EXAMPLES_USAGE.to_string()),
+        "grep" => Some(mini_grep::usage()),
+        "guess" => Some(GUESS_USAGE.to_string()),
+        "help" => Some(USAGE.to_string()),
+        _ => None,
+    }
+}
+
+fn is_help(arg: Option<&String>) -> bool {
+    matches!(arg.map(String::as_str), Some("-h" | "--help"))
+}
+
+// args[0] is the program name, args[1] picks the command
+// every command gets the arguments from its own name onwards, the same shape as env::args()
+pub fn dispatch(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let Some(command) = args.get(1) else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+    let args = &args[1..];
+    if is_help(args.get(1)) {
+        if let Some(help) = help(command) {
+            println!("{help}");
+            return Ok(());
+        }
+    }
+    match command.as_str() {
+        "examples" => examples_command(args),
+        "grep" => grep_command(args),
+        "guess" => guess_command(args),
+        "help" | "-h" | "--help" => match args.get(1) {
+            None => {
+                println!("{USAGE}");
+                Ok(())
+            }
+            Some(command) => match help(command) {
+                Some(help) => {
+                    println!("{help}");
+                    Ok(())
+                }
+                None => Err(unknown("command", command, COMMANDS.iter().copied()).into()),
+            },
+        },
+        other => Err(unknown("command", other, COMMANDS.iter().copied()).into()),
+    }
+}
+
+// `grep index` and `grep query` work on the word index, anything else is a search,
+// the words index and query themselves are searched for after --
+fn grep_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.get(1).map(String::as_str) {
+        Some("index") => mini_grep::index_command(&args[1..]),
+        Some("query") => mini_grep::query_command(&args[1..]),
+        _ => mini_grep::mini_grep(args),
+    }
+}
+
+fn guess_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.get(1).map(String::as_str) {
+        Some("leaderboard") => return leaderboard_command(&args[2..]),
+        Some("serve") => return serve_command(&args[2..]),
+        Some("connect") => return connect_command(&args[2..]),
+        Some("replay") => return replay_command(&args[2..]),
+        _ => {}
+    }
+    let (mut rest, mut script) = scripted_input(&args[1..])?;
+    let player = take_option(&mut rest, "--name")?.map_or_else(default_learner, str::to_string);
+    let log = take_option(&mut rest, "--record")?;
+    let words = match take_option(&mut rest, "--words")? {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("can not read {path}: {e}"))?,
+        None => words::POEM.to_string(),
+    };
+    let mut config =
+        guessing_game::Config::build(&rest).map_err(|e| format!("{e}\n\n{GUESS_USAGE}"))?;
+    // a log is played again as number guesses, so the other modes can not be recorded
+    if log.is_some() && !matches!(config.mode, Mode::Play) {
+        return Err(
+            "--record only records the number game, not --solver, --reverse, --wordle or --hangman"
+                .into(),
+        );
+    }
+    // the word games pick a word, not a number, so the flags for the number range mean nothing there
+    if matches!(config.mode, Mode::Wordle | Mode::Hangman) {
+        let number_flag = rest
+            .iter()
+            .map(|arg| arg.split_once('=').map_or(*arg, |(flag, _)| flag))
+            .find(|flag| matches!(*flag, "--range" | "--difficulty" | "--hints"));
+        if let Some(flag) = number_flag {
+            return Err(
+                format!("{flag} only works in the number game, not --wordle or --hangman").into(),
+            );
+        }
+    }
+    // a recorded game can only be played again with the seed that picked its secret
+    if log.is_some() && config.seed.is_none() {
+        config.seed = Some(rand::random());
+    }
+    let mut stdout = io::stdout().lock();
+    let mut stdin;
+    let input: &mut dyn BufRead = match &mut script {
+        Some(script) => script,
+        None => {
+            stdin = io::stdin().lock();
+            &mut stdin
+        }
+    };
+    match config.mode {
+        // only games the player guessed in go on the leaderboard
+        Mode::Play => {
+            let started = progress::now();
+            let (result, transcript) = recorded_game(&mut stdout, input, &config)?;
+            if let Some(log) = log {
+                Session::new(&config, started, transcript)?
+                    .append(Path::new(log))
+                    .map_err(|e| format!("could not record the game in {log}: {e}"))?;
+            }
+            if let Some(result) = result {
+                record_game(&player, result);
+            }
+        }
+        Mode::Solver => guessing_game::solve(&mut stdout, &config)?,
+        Mode::Reverse => guessing_game::reversed(&mut stdout, input, &config)?,
+        Mode::Wordle => words::wordle(&mut stdout, input, &config, &words::word_list(&words))?,
+        Mode::Hangman => words::hangman(&mut stdout, input, &config, &words::word_list(&words))?,
+    }
+    match &script {
+        Some(script) => check_script(script),
+        None => Ok(()),
+    }
+}
+
+// like the progress file, a leaderboard that can not be written should not spoil the game
+fn record_game(player: &str, result: GameResult) {
+    let Some(path) = leaderboard_path() else {
+        return;
+    };
+    if let Err(e) = Leaderboard::record(&path, player, result) {
+        eprintln!("could not record the game: {e}");
+    }
+}
+
+// the game options apply to the shared game, --attempts to every player
+fn serve_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let port = match take_option(&mut args, "--port")? {
+        Some(port) => port
+            .parse()
+            .map_err(|_| format!("--port takes a port number, not '{port}'"))?,
+        None => server::DEFAULT_PORT,
+    };
+    let config =
+        guessing_game::Config::build(&args).map_err(|e| format!("{e}\n\n{GUESS_USAGE}"))?;
+    // every address, so the others on the network can join
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    println!(
+        "waiting for players on port {port}, join with `rust-book-examples guess connect <this host>:{port}` or netcat"
+    );
+    server::serve(listener, &config, &mut io::stdout())?;
+    Ok(())
+}
+
+fn connect_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (mut rest, script) = scripted_input(args)?;
+    let name = take_option(&mut rest, "--name")?.map_or_else(default_learner, str::to_string);
+    let address = match rest.as_slice() {
+        [] => format!("127.0.0.1:{}", server::DEFAULT_PORT),
+        // a host without a port gets the one the server uses by default
+        [host] if !host.contains(':') => format!("{host}:{}", server::DEFAULT_PORT),
+        [address] => address.to_string(),
+        [_, extra, ..] => {
+            return Err(format!("unexpected argument '{extra}'\n\n{GUESS_USAGE}").into())
+        }
+    };
+    let input: Box<dyn BufRead + Send> = match script {
+        Some(script) => Box::new(script),
+        None => Box::new(io::BufReader::new(io::stdin())),
+    };
+    server::connect(address.as_str(), &name, input, &mut io::stdout())?;
+    Ok(())
+}
+
+fn replay_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (log, steps) = match args.as_slice() {
+        [log] => (*log, false),
+        [log, "--steps"] | ["--steps", log] => (*log, true),
+        _ => return Err(format!("replay needs the log file to play back\n\n{GUESS_USAGE}").into()),
+    };
+    let sessions = recording::load(Path::new(log))?;
+    let mut stdout = io::stdout().lock();
+    if steps {
+        recording::show_steps(&sessions, &mut stdout, &mut io::stdin().lock())?;
+    } else {
+        recording::show_stats(&sessions, &mut stdout)?;
+    }
+    Ok(())
+}
+
+fn leaderboard_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let only = match args {
+        [] => None,
+        [level] => Some(guessing_game::Difficulty::parse(level)?.name()),
+        _ => return Err(format!("unexpected argument '{}'\n\n{GUESS_USAGE}", args[1]).into()),
+    };
+    let path = leaderboard_path()
+        .ok_or("can not tell where the leaderboard is, set RUST_BOOK_LEADERBOARD")?;
+    let leaderboard = Leaderboard::load(&path)?;
+    leaderboard::show(&leaderboard, only, &mut io::stdout().lock())?;
+    Ok(())
+}
+
+fn examples_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let names = || examples::REGISTRY.iter().map(|example| example.name());
+    match args.get(1).map(String::as_str) {
+        Some("list") => {
+            let mut chapter = 0;
+            for example in examples::REGISTRY {
+                if example.chapter() != chapter {
+                    chapter = example.chapter();
+                    println!("chapter {chapter}: {}", examples::chapter_title(chapter));
+                }
+                println!("  {:<27} {}", example.name(), example.description());
+            }
+            Ok(())
+        }
+        Some("run") => {
+            let (mut rest, mut script) = scripted_input(&args[2..])?;
+            let learner =
+                take_option(&mut rest, "--user")?.map_or_else(default_learner, str::to_string);
+            if let Some(extra) = rest.get(1) {
+                return Err(format!("unexpected argument '{extra}'\n\n{EXAMPLES_USAGE}").into());
+            }
+            let to_run: Vec<&dyn Example> = match rest.first().copied() {
+                Some("--all") => examples::REGISTRY.to_vec(),
+                Some(name) => match examples::find(name) {
+                    Some(example) => vec![example],
+                    None => return Err(unknown("example", name, names()).into()),
+                },
+                None => return Err(format!("which example?\n\n{EXAMPLES_USAGE}").into()),
+            };
+            for example in to_run {
+                run_example(example, script.as_mut())?;
+                record_run(&learner, example.name());
+            }
+            Ok(())
+        }
+        Some("progress") => progress_command(&args[2..]),
+        Some("search") => {
+            if args.len() < 3 {
+                return Err(format!("what should be searched for?\n\n{EXAMPLES_USAGE}").into());
+            }
+            // the words are joined again, so `search if let` works without quotes
+            let query = args[2..].join(" ");
+            let hits = search_sources(&query);
+            if hits.is_empty() {
+                return Err(format!("no example contains '{query}'").into());
+            }
+            print_hits(&hits, &mut io::stdout().lock())?;
+            Ok(())
+        }
+        Some("quiz") => {
+            let (rest, script) = scripted_input(&args[2..])?;
+            let mut chosen = Vec::new();
+            for name in rest {
+                match examples::find(name) {
+                    Some(example) => chosen.push(example),
+                    None => return Err(unknown("example", name, names()).into()),
+                }
+            }
+            if chosen.is_empty() {
+                chosen = examples::REGISTRY.to_vec();
+            }
+            if chosen.iter().all(|example| example.quiz().is_empty()) {
+                let with_questions = examples::REGISTRY
+                    .iter()
+                    .filter(|example| !example.quiz().is_empty())
+                    .map(|example| example.name())
+                    .collect::<Vec<_>>();
+                return Err(
+                    format!("no questions there yet, try {}", with_questions.join(", ")).into(),
+                );
+            }
+            let mut stdout = io::stdout().lock();
+            match script {
+                Some(mut script) => quiz(&chosen, &mut stdout, &mut script)?,
+                None => quiz(&chosen, &mut stdout, &mut io::stdin().lock())?,
+            };
+            Ok(())
+        }
+        Some("batch") => {
+            let (rest, script) = scripted_input(&args[2..])?;
+            if let Some(extra) = rest.first() {
+                return Err(format!("unexpected argument '{extra}'\n\n{EXAMPLES_USAGE}").into());
+            }
+            let mut stdout = io::stdout().lock();
+            let batch = run_batch(
+                examples::REGISTRY,
+                &mut stdout,
+                &mut script.unwrap_or_default(),
+            );
+            writeln!(stdout)?;
+            batch.report(&mut stdout)?;
+            let failed = batch.outcomes.len() - batch.passed();
+            if failed > 0 {
+                return Err(
+                    format!("{failed} of {} examples did not pass", batch.outcomes.len()).into(),
+                );
+            }
+            Ok(())
+        }
+        Some("explain-error") => match args.get(2) {
+            None => {
+                for snippet in compile_fail::CATALOG {
+                    println!("  {:<29} {}", snippet.name, snippet.module);
+                }
+                Ok(())
+            }
+            Some(name) => match compile_fail::find(name) {
+                Some(snippet) => {
+                    snippet.explain(&mut io::stdout().lock())?;
+                    Ok(())
+                }
+                None => {
+                    let names = compile_fail::CATALOG.iter().map(|snippet| snippet.name);
+                    Err(unknown("snippet", name, names).into())
+                }
+            },
+        },
+        Some("export") => {
+            let (rest, script) = scripted_input(&args[2..])?;
+            let mut format = Format::Markdown;
+            let mut dir = None;
+            let mut rest = rest.into_iter();
+            while let Some(arg) = rest.next() {
+                match arg.split_once('=') {
+                    Some(("--format", name)) => format = Format::parse(name)?,
+                    _ if arg == "--format" => match rest.next() {
+                        Some(name) => format = Format::parse(name)?,
+                        None => return Err("--format needs a value".into()),
+                    },
+                    _ if dir.is_none() && !arg.starts_with('-') => dir = Some(arg),
+                    _ => {
+                        return Err(
+                            format!("unexpected argument '{arg}'\n\n{EXAMPLES_USAGE}").into()
+                        )
+                    }
+                }
+            }
+            let Some(dir) = dir else {
+                return Err(format!("where should the pages go?\n\n{EXAMPLES_USAGE}").into());
+            };
+            let written = export::export(
+                examples::REGISTRY,
+                format,
+                Path::new(dir),
+                &mut script.unwrap_or_default(),
+            )?;
+            export::report(&written, &mut io::stdout().lock())?;
+            Ok(())
+        }
+        Some(other) => Err(unknown(
+            "examples command",
+            other,
+            [
+                "list",
+                "run",
+                "batch",
+                "explain-error",
+                "export",
+                "progress",
+                "quiz",
+                "search",
+            ],
+        )
+        .into()),
+        None => {
+            println!("{EXAMPLES_USAGE}");
+            Ok(())
+        }
+    }
+}
+
+fn run_example(example: &dyn Example, script: Option<&mut Script>) -> Result<(), Box<dyn Error>> {
+    match script {
+        Some(script) => {
+            script.rewind();
+            example.run(&mut io::stdout().lock(), script)?;
+            check_script(script)
+        }
+        None => example.run(&mut io::stdout().lock(), &mut io::stdin().lock()),
+    }
+}
+
+// a progress file that can not be written should not spoil the run itself
+fn record_run(learner: &str, example: &str) {
+    let Some(path) = progress_path() else {
+        return;
+    };
+    let saved = Progress::update(&path, |progress| {
+        progress.record_run(learner, example, progress::now())
+    });
+    if let Err(e) = saved {
+        eprintln!("could not record the run: {e}");
+    }
+}
+
+fn progress_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let learner = take_option(&mut args, "--user")?.map_or_else(default_learner, str::to_string);
+    let path =
+        progress_path().ok_or("can not tell where to keep progress, set RUST_BOOK_PROGRESS")?;
+    let progress = Progress::load(&path)?;
+    match args.as_slice() {
+        [] | ["show"] => {
+            let learner_progress = progress.learner(&learner);
+            progress::show(
+                &learner,
+                &learner_progress,
+                examples::REGISTRY,
+                progress::now(),
+                &mut io::stdout().lock(),
+            )?;
+        }
+        ["complete", name] => {
+            let Some(example) = examples::find(name) else {
+                let names = examples::REGISTRY.iter().map(|example| example.name());
+                return Err(unknown("example", name, names).into());
+            };
+            Progress::update(&path, |progress| {
+                progress.mark_complete(&learner, example.name(), progress::now())
+            })?;
+            println!("marked {} as complete for {learner}", example.name());
+        }
+        ["reset"] => {
+            if Progress::update(&path, |progress| progress.reset(&learner))? {
+                println!("forgot the progress of {learner}");
+            } else {
+                println!("{learner} has no progress to forget");
+            }
+        }
+        ["export"] => println!("{}", progress.to_json().pretty()),
+        ["export", file] => {
+            fs::write(file, progress.to_json().pretty() + "\n")?;
+            println!("wrote {file}");
+        }
+        [other, ..] => {
+            let commands = ["show", "complete", "reset", "export"];
+            if commands.contains(other) {
+                return Err(format!("wrong arguments for {other}\n\n{EXAMPLES_USAGE}").into());
+            }
+            return Err(unknown("progress command", other, commands).into());
+        }
+    }
+    Ok(())
+}
+
+// removes `flag value` or `flag=value` from args and gives back the value
+fn take_option<'a>(args: &mut Vec<&'a str>, flag: &str) -> Result<Option<&'a str>, String> {
+    let Some(at) = args.iter().position(|arg| {
+        *arg == flag
+            || arg
+                .strip_prefix(flag)
+                .is_some_and(|rest| rest.starts_with('='))
+    }) else {
+        return Ok(None);
+    };
+    let arg = args.remove(at);
+    if let Some(value) = arg
+        .strip_prefix(flag)
+        .and_then(|rest| rest.strip_prefix('='))
+    {
+        return Ok(Some(value));
+    }
+    if at < args.len() {
+        return Ok(Some(args.remove(at)));
+    }
+    Err(format!("{flag} needs a value"))
+}
+
+// pulls --input and --input-file out of args, giving back the other arguments
+// and the answers to use instead of stdin, if any were given
+// an example or game that wanted more answers than --input gave did not finish
+fn check_script(script: &Script) -> Result<(), Box<dyn Error>> {
+    if script.ran_out() {
+        return Err(
+            "the scripted answers ran out before it finished, add more with --input".into(),
+        );
+    }
+    Ok(())
+}
+
+fn scripted_input(args: &[String]) -> Result<(Vec<&str>, Option<Script>), String> {
+    let mut rest = Vec::new();
+    let mut script = Script::new();
+    let mut scripted = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        if flag != "--input" && flag != "--input-file" {
+            rest.push(arg.as_str());
+            continue;
+        }
+        let value = match inline.or_else(|| args.next().cloned()) {
+            Some(value) => value,
+            None => return Err(format!("{flag} needs a value")),
+        };
+        if flag == "--input" {
+            script.push(&value);
+        } else {
+            let contents = fs::read_to_string(&value)
+                .map_err(|e| format!("can not read input file {value}: {e}"))?;
+            script.push(&contents);
+        }
+        scripted = true;
+    }
+    Ok((rest, scripted.then_some(script)))
+}
+
+// builds the error for a name we do not know, suggesting the closest ones we do
+pub fn unknown<'a>(
+    kind: &str,
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> String {
+    let suggestions = suggest(name, candidates);
+    match suggestions.as_slice() {
+        [] => format!("unknown {kind} '{name}'"),
+        [only] => format!("unknown {kind} '{name}', did you mean '{only}'?"),
+        many => format!(
+            "unknown {kind} '{name}', did you mean one of: {}?",
+            many.join(", ")
+        ),
+    }
+}
+
+// candidates that are a few typos away from name, or start with it, closest first
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let allowed = (name.chars().count() / 3).max(1);
+    let mut close: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= allowed || candidate.starts_with(name))
+        .collect();
+    close.sort();
+    close.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+// the levenshtein distance, how many single character edits turn a into b
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(ca != *cb);
+            current.push(substitute.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use std::io::BufRead;
+
+    use super::*;
+
+    #[test]
+    fn suggests_close_names() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(
+            suggest("exmaples", COMMANDS.iter().copied()),
+            vec!["examples"]
+        );
+        assert_eq!(suggest("gues", COMMANDS.iter().copied()), vec!["guess"]);
+        assert!(suggest("zzz", COMMANDS.iter().copied()).is_empty());
+        assert_eq!(
+            unknown("example", "struct", ["structs", "slices"]),
+            "unknown example 'struct', did you mean 'structs'?"
+        );
+    }
+
+    #[test]
+    fn input_flags_become_a_script() {
+        let args: Vec<String> = ["data_types", "--input", "3", "--input=4\n5"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let (rest, script) = scripted_input(&args).unwrap();
+        assert_eq!(rest, vec!["data_types"]);
+        let mut answers = String::new();
+        let mut script = script.unwrap();
+        while script.read_line(&mut answers).unwrap() > 0 {}
+        assert_eq!(answers, "3\n4\n5\n");
+
+        let (_, script) = scripted_input(&args[..1]).unwrap();
+        assert!(script.is_none());
+        assert!(scripted_input(&args[..2]).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+// runs examples one after another, each in isolation, so one that panics or fails
+// does not stop the rest, and reports how every one of them went at the end
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+    time::{Duration, Instant},
+};
+
+use super::{script::Script, Example};
+
+pub enum Status {
+    Passed,
+    Panicked(String),
+    Failed(String),
+}
+
+pub struct Outcome {
+    pub name: &'static str,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+pub struct Batch {
+    pub outcomes: Vec<Outcome>,
+    pub elapsed: Duration,
+}
+
+thread_local! {
+    // set while an example runs, so its panic is recorded instead of printed
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// the default hook prints the panic to stderr, which would end up in the middle of the output
+// it stays in charge of every panic outside a batch, like those of other threads or tests
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if QUIET.with(Cell::get) {
+                let location = info.location().map(|l| l.to_string());
+                LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+// panic! with a literal gives a &str, a formatted message gives a String
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked with a value that is not a message".to_string()
+    };
+    match LOCATION.with(|cell| cell.borrow_mut().take()) {
+        Some(location) => format!("{message} (at {location})"),
+        None => message,
+    }
+}
+
+// every example gets the answers of the script from the start
+// with no answers at all the examples that ask for input fail instead of waiting on stdin
+pub fn run_batch(examples: &[&dyn Example], out: &mut dyn Write, script: &mut Script) -> Batch {
+    install_hook();
+    let start = Instant::now();
+    let mut outcomes = Vec::new();
+    for example in examples {
+        script.rewind();
+        let started = Instant::now();
+        QUIET.with(|quiet| quiet.set(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| example.run(out, script)));
+        QUIET.with(|quiet| quiet.set(false));
+        let status = match result {
+            Ok(Ok(())) => Status::Passed,
+            Ok(Err(e)) => Status::Failed(e.to_string()),
+            Err(payload) => Status::Panicked(panic_message(payload.as_ref())),
+        };
+        outcomes.push(Outcome {
+            name: example.name(),
+            status,
+            elapsed: started.elapsed(),
+        });
+    }
+    Batch {
+        outcomes,
+        elapsed: start.elapsed(),
+    }
+}
+
+impl Batch {
+    pub fn passed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome.status, Status::Passed))
+            .count()
+    }
+
+    pub fn report(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{:<27} {:<6} {:>10}", "example", "result", "time")?;
+        for outcome in &self.outcomes {
+            let result = match outcome.status {
+                Status::Passed => "pass",
+                Status::Panicked(_) => "panic",
+                Status::Failed(_) => "error",
+            };
+            writeln!(
+                out,
+                "{:<27} {:<6} {:>10}",
+                outcome.name,
+                result,
+                format!("{:.1?}", outcome.elapsed)
+            )?;
+        }
+
+        // the messages go below the table so long ones do not break up the columns
+        let problems: Vec<_> = self
+            .outcomes
+            .iter()
+            .filter_map(|outcome| match &outcome.status {
+                Status::Passed => None,
+                Status::Panicked(message) => Some((outcome.name, "panicked", message)),
+                Status::Failed(message) => Some((outcome.name, "failed", message)),
+            })
+            .collect();
+        if !problems.is_empty() {
+            writeln!(out)?;
+        }
+        for (name, what, message) in problems {
+            writeln!(out, "{name} {what}: {message}")?;
+        }
+
+        let panicked = self
+            .outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome.status, Status::Panicked(_)))
+            .count();
+        writeln!(
+            out,
+            "\n{} examples: {} passed, {} panicked, {} failed in {:.1?}",
+            self.outcomes.len(),
+            self.passed(),
+            panicked,
+            self.outcomes.len() - self.passed() - panicked,
+            self.elapsed
+        )
+    }
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use std::{error::Error, io::BufRead};
+
+    use super::*;
+    use crate::examples::{data_types::DataTypes, hello_world::HelloWorld};
+
+    // indexes past the end of a vector, which data_types used to do before it checked the index
+    struct Panics;
+
+    impl Example for Panics {
+        fn name(&self) -> &'static str {
+            "panics"
+        }
+
+        fn chapter(&self) -> u32 {
+            3
+        }
+
+        fn description(&self) -> &'static str {
+            "an index out of bounds"
+        }
+
+        fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+            let empty: Vec<i32> = Vec::new();
+            writeln!(out, "{}", empty[0])?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn keeps_going_after_a_panic_or_an_error() {
+        let examples: &[&dyn Example] = &[&Panics, &HelloWorld, &DataTypes];
+        let mut out = Vec::new();
+
+        let mut script = Script::new();
+        script.push("9");
+        let batch = run_batch(examples, &mut out, &mut script);
+        assert_eq!(batch.passed(), 1);
+        assert!(matches!(
+            &batch.outcomes[0].status,
+            Status::Panicked(message) if message.contains("index out of bounds")
+                && message.contains("batch.rs")
+        ));
+        // an index past the end of the array is an error
+        assert!(matches!(
+            &batch.outcomes[2].status,
+            Status::Failed(message) if message.contains("9 is above the range")
+        ));
+
+        // no answers at all is an error, not a hang
+        let batch = run_batch(examples, &mut out, &mut Script::new());
+        assert!(matches!(&batch.outcomes[2].status, Status::Failed(_)));
+
+        let mut report = Vec::new();
+        batch.report(&mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("hello_world                 pass"));
+        assert!(report.contains("data_types failed: the input ended before an answer was given"));
+        assert!(report.contains("3 examples: 1 passed, 1 panicked, 1 failed in"));
+    }
+}
@@ -1,13 +1,40 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct Borrowing;
+
+impl Example for Borrowing {
+    fn name(&self) -> &'static str {
+        "borrowing"
+    }
+
+    fn chapter(&self) -> u32 {
+        4
+    }
+
+    fn description(&self) -> &'static str {
+        "references, mutable references and dangling references"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        borrowing(out)?;
+        Ok(())
+    }
+}
 
 #[allow(unused)]
-pub fn borrowing() {
+pub fn borrowing(out: &mut dyn Write) -> io::Result<()> {
     // using a reference for a function parameter
     fn calculate_length(s: &String) -> usize {
         s.len()
     }
     let s1 = String::from("hello");
     let len = calculate_length(&s1);
-    println!("The length of '{}' is {}.", s1, len);
+    writeln!(out, "The length of '{}' is {}.", s1, len)?;
 
     // in order to change areference, you need to declare it mutable
     fn change(some_string: &mut String) {
@@ -15,7 +42,7 @@ pub fn borrowing() {
     }
     let mut s = String::from("hello");
     change(&mut s);
-    println!("the value of s is: {s}");
+    writeln!(out, "the value of s is: {s}")?;
 
     // you can only have one mut reference to a value at a time
     // the following code causes an error
@@ -23,15 +50,16 @@ pub fn borrowing() {
     // let r1 = &mut s;
     // let r2 = &mut s;
     // println!("{}, {}", r1, r2);
+    // see `examples explain-error two-mutable-borrows`
 
     // you can create multiple references, as long as they are at different scopes
     let mut s = String::from("hello");
     {
         let r1 = &mut s;
-        println!("Reference 1: {r1}");
+        writeln!(out, "Reference 1: {r1}")?;
     }
     let r2 = &mut s;
-    println!("Reference 2: {r2}");
+    writeln!(out, "Reference 2: {r2}")?;
 
     // we cannot have a mutable reference while we also have an immutable reference to the same value
     // the following code causes an error
@@ -40,14 +68,15 @@ pub fn borrowing() {
     // let r2 = &s; // no problem
     // let r3 = &mut s; // BIG PROBLEM
     // println!("{}, {}, and {}", r1, r2, r3);
+    // see `examples explain-error mutable-and-immutable-borrow`
 
     // a reference goes out of scope after it is used (or the last time it is used)
     let mut s = String::from("hello");
     let r1 = &s;
     let r2 = &s;
-    println!("{} and {}", r1, r2);
+    writeln!(out, "{} and {}", r1, r2)?;
     let r3 = &mut s;
-    println!("{}", r3);
+    writeln!(out, "{}", r3)?;
 
     // a dangling reference is when we have a reference which points to a value that doesn't exist anymore
     // rust prevents dangling reference and will not let us compile if we have them
@@ -57,6 +86,7 @@ pub fn borrowing() {
     //     &s
     // }
     // let ref_to_nothing = dangle();
+    // see `examples explain-error dangling-reference`
 
     // instead of returning a reference, return the value instead
     fn no_dangle() -> String {
@@ -64,5 +94,6 @@ pub fn borrowing() {
         s
     }
     let the_value = no_dangle();
-    println!("The value is: {the_value}");
+    writeln!(out, "The value is: {the_value}")?;
+    Ok(())
 }
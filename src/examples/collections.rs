@@ -1,5 +1,33 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct Collections;
+
+impl Example for Collections {
+    fn name(&self) -> &'static str {
+        "collections"
+    }
+
+    fn chapter(&self) -> u32 {
+        8
+    }
+
+    fn description(&self) -> &'static str {
+        "vectors, strings and hash maps"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        collections(out)?;
+        Ok(())
+    }
+}
+
 #[allow(unused)]
-pub fn collections() {
+pub fn collections(out: &mut dyn Write) -> io::Result<()> {
     // empty vector
     let _v: Vec<i32> = Vec::new();
 
@@ -18,14 +46,14 @@ pub fn collections() {
     // you can access the vector using get or the [] syntax
     // using [] is straighforward but more error prone
     let third: &i32 = &v[2];
-    println!("the third element is {third}");
+    writeln!(out, "the third element is {third}")?;
 
     // using .get requires us to extract the value from Option
     // this is a safer option if you are not sure the element will exist at runtime
     let third: Option<&i32> = v.get(2);
     match third {
-        Some(third) => println!("the third element is still {third}"),
-        None => println!("there is no third element!"),
+        Some(third) => writeln!(out, "the third element is still {third}")?,
+        None => writeln!(out, "there is no third element!")?,
     }
 
     // the borrow checker prevents us from adding items to a vector if a reference to it is held
@@ -34,11 +62,12 @@ pub fn collections() {
     // let first = &v[0];
     // v.push(6);
     // println!("the first element is {first}");
+    // see `examples explain-error push-while-borrowed`
 
     // iterating through a vector
     let v = vec![100, 200, 300];
     for i in &v {
-        println!("{i}");
+        writeln!(out, "{i}")?;
     }
 
     // making changes to all items in a vector
@@ -76,45 +105,46 @@ pub fn collections() {
     // strings can change in size
     let mut s = String::from("foo");
     s.push_str("bar");
-    println!("I grew: {s}");
+    writeln!(out, "I grew: {s}")?;
 
     // push takes a single char and adds it to the string
     let mut s = String::from("lo");
     s.push('l');
-    println!("haha: {s}");
+    writeln!(out, "haha: {s}")?;
 
     // string concatenation
     let s1 = String::from("Hello, ");
     let s2 = String::from("world!");
     let s3 = s1 + &s2; // note s1 has been moved here and can no longer be used
-    println!("{s3}");
+    writeln!(out, "{s3}")?;
 
     // using format! to combine strings is easier
     let s1 = String::from("tic");
     let s2 = String::from("tac");
     let s3 = String::from("toe");
     let s = format!("{s1}-{s2}-{s3}");
-    println!("formatted string: {s}");
+    writeln!(out, "formatted string: {s}")?;
 
     // rust does not support string indexing like other languages
     // the following code throws an error
     // let s1 = String::from("hello");
     // let h = s1[0];
+    // see `examples explain-error string-index`
 
     // not every character contains the same amount of bytes
     // take the following example
     let hello = "Здравствуйте"; // each character here is 2 bytes each
     let s = &hello[0..4]; // dont assume this gets you the first 4 characters it gets the first 4 bytes
-    println!("I only contain 2 charaters! {s}");
+    writeln!(out, "I only contain 2 charaters! {s}")?;
 
     // iterating over a string as characters
     for c in "asdasdasdaa".chars() {
-        println!("{c}");
+        writeln!(out, "{c}")?;
     }
 
     // iterating over a string as bytes
     for b in "adasdasdas".bytes() {
-        println!("{b}");
+        writeln!(out, "{b}")?;
     }
 
     // creating an empty hash map
@@ -127,11 +157,11 @@ pub fn collections() {
     let team_name = String::from("Blue");
     // below, unwrap or sets the score to 0 if the hash map doesnt have a key for the provided value
     let score = scores.get(&team_name).copied().unwrap_or(0);
-    println!("blue team score is: {score}");
+    writeln!(out, "blue team score is: {score}")?;
 
     // iterating through a hash map
     for (key, value) in &scores {
-        println!("{key}: {value}");
+        writeln!(out, "{key}: {value}")?;
     }
 
     // hash maps take ownership of values
@@ -147,14 +177,14 @@ pub fn collections() {
     let mut scores = HashMap::new();
     scores.insert(String::from("Blue"), 10);
     scores.insert(String::from("Blue"), 25);
-    println!("{:?}", scores);
+    writeln!(out, "{:?}", scores)?;
 
     // checking if a hashmap has key, if it does, do nothing, if it doesn't, add a value to the key
     let mut scores = HashMap::new();
     scores.insert(String::from("Blue"), 10);
     scores.entry(String::from("Yellow")).or_insert(50);
     scores.entry(String::from("Blue")).or_insert(50);
-    println!("{:?}", scores);
+    writeln!(out, "{:?}", scores)?;
 
     // updating a value in a hashmap based on a previous value
     let text = "hello world wonderful world";
@@ -163,5 +193,6 @@ pub fn collections() {
         let count = map.entry(word).or_insert(0);
         *count += 1;
     }
-    println!("{:?}", map);
+    writeln!(out, "{:?}", map)?;
+    Ok(())
 }
@@ -0,0 +1,285 @@
+// the code from the examples that is there to show what the compiler will not let us do
+// it can not live in the modules themselves without breaking the build, so it is kept here
+// and compiled with the local rustc on demand, to show the real error next to the explanation
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    process::Command,
+};
+
+pub struct Snippet {
+    pub name: &'static str,
+    // the example module the snippet belongs to
+    pub module: &'static str,
+    // the error code rustc gives for it, the test below makes sure it still does
+    pub error: &'static str,
+    pub explanation: &'static str,
+    // statements that are compiled as the body of main
+    pub code: &'static str,
+}
+
+pub const CATALOG: &[Snippet] = &[
+    Snippet {
+        name: "moved-value",
+        module: "ownership",
+        error: "E0382",
+        explanation: "assigning a String to another variable moves it, s1 is no longer valid afterwards",
+        code: r#"
+let s1 = String::from("hello");
+let s2 = s1;
+println!("{}, world!", s1);
+"#,
+    },
+    Snippet {
+        name: "moved-into-function",
+        module: "ownership",
+        error: "E0382",
+        explanation: "passing a String to a function moves it, so s does not exist in this scope any longer",
+        code: r#"
+fn takes_ownership(some_string: String) {
+    println!("This string is mine: {some_string}");
+}
+let s = String::from("hello");
+takes_ownership(s);
+println!("{s}");
+"#,
+    },
+    Snippet {
+        name: "two-mutable-borrows",
+        module: "borrowing",
+        error: "E0499",
+        explanation: "you can only have one mut reference to a value at a time",
+        code: r#"
+let mut s = String::from("hello");
+let r1 = &mut s;
+let r2 = &mut s;
+println!("{}, {}", r1, r2);
+"#,
+    },
+    Snippet {
+        name: "mutable-and-immutable-borrow",
+        module: "borrowing",
+        error: "E0502",
+        explanation: "we cannot have a mutable reference while we also have an immutable reference to the same value",
+        code: r#"
+let mut s = String::from("hello");
+let r1 = &s; // no problem
+let r2 = &s; // no problem
+let r3 = &mut s; // BIG PROBLEM
+println!("{}, {}, and {}", r1, r2, r3);
+"#,
+    },
+    Snippet {
+        name: "dangling-reference",
+        module: "borrowing",
+        error: "E0106",
+        explanation: "dangle returns a reference to a value that is dropped when the function ends, \
+            rust will not let us create a dangling reference",
+        code: r#"
+fn dangle() -> &String {
+    let s = String::from("hello");
+    &s
+}
+let ref_to_nothing = dangle();
+"#,
+    },
+    Snippet {
+        name: "option-plus-number",
+        module: "enums",
+        error: "E0277",
+        explanation: "Option<i8> is a different type from i8, the value has to be taken out of the option first",
+        code: r#"
+let x: i8 = 5;
+let y: Option<i8> = Some(5);
+let sum = x + y;
+"#,
+    },
+    Snippet {
+        name: "push-while-borrowed",
+        module: "collections",
+        error: "E0502",
+        explanation: "pushing may move the vector to a new place in memory, \
+            so the borrow checker does not allow it while a reference into it is held",
+        code: r#"
+let mut v = vec![1, 2, 3, 4, 5];
+let first = &v[0];
+v.push(6);
+println!("the first element is {first}");
+"#,
+    },
+    Snippet {
+        name: "string-index",
+        module: "collections",
+        error: "E0277",
+        explanation: "rust does not support string indexing, a character can take more than one byte",
+        code: r#"
+let s1 = String::from("hello");
+let h = s1[0];
+"#,
+    },
+    Snippet {
+        name: "number-as-condition",
+        module: "control_flow",
+        error: "E0308",
+        explanation: "you cannot use values as booleans unless they are specifically bool",
+        code: r#"
+let number = 3;
+if number {
+    println!("number was three");
+}
+"#,
+    },
+    Snippet {
+        name: "if-arms-differ",
+        module: "control_flow",
+        error: "E0308",
+        explanation: "both outcomes of an if used as a value must be of the same type",
+        code: r#"
+let condition = true;
+let number = if condition { 5 } else { "six" };
+println!("The value of number is: {number}");
+"#,
+    },
+    Snippet {
+        name: "mixed-point-types",
+        module: "generics_traits_lifetimes",
+        error: "E0308",
+        explanation: "Point<T> has one type T, so x and y can not be an integer and a float at the same time",
+        code: r#"
+struct Point<T> {
+    x: T,
+    y: T,
+}
+let wont_work = Point { x: 5, y: 1.2 };
+"#,
+    },
+    Snippet {
+        name: "longest-without-lifetimes",
+        module: "generics_traits_lifetimes",
+        error: "E0106",
+        explanation: "rust can not tell whether the returned reference comes from x or y, \
+            so the lifetimes have to be written out",
+        code: r#"
+fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+"#,
+    },
+    Snippet {
+        name: "does-not-live-long-enough",
+        module: "generics_traits_lifetimes",
+        error: "E0597",
+        explanation: "longest says the result lives as long as both arguments, \
+            but s2 is dropped at the end of the inner scope while result is still used",
+        code: r#"
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+let s1 = String::from("this is longer than the other");
+let result;
+{
+    let s2 = String::from("shorter");
+    result = longest(s1.as_str(), s2.as_str());
+}
+println!("the longest string is {}", result);
+"#,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Snippet> {
+    CATALOG.iter().find(|snippet| snippet.name == name)
+}
+
+impl Snippet {
+    // the snippet as a program of its own
+    pub fn source(&self) -> String {
+        let body: String = self
+            .code
+            .trim()
+            .lines()
+            .map(|line| format!("    {line}\n"))
+            .collect();
+        format!("fn main() {{\n{body}}}\n")
+    }
+
+    // compiles the snippet with rustc and gives back what it printed
+    // Ok means it failed to compile like it should, an Err means it could not be run, or it compiled
+    pub fn compile(&self) -> Result<String, String> {
+        let dir = env::temp_dir().join(format!(
+            "rust-book-examples-{}-{}",
+            std::process::id(),
+            self.name
+        ));
+        fs::create_dir_all(&dir).map_err(|e| format!("can not create {}: {e}", dir.display()))?;
+        let file = format!("{}.rs", self.name.replace('-', "_"));
+        fs::write(dir.join(&file), self.source())
+            .map_err(|e| format!("can not write {file}: {e}"))?;
+
+        // RUSTC is what cargo uses to pick the compiler as well
+        // it runs inside the directory so the diagnostic shows the file name and not the whole path
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let output = Command::new(&rustc)
+            .args([
+                "--edition",
+                "2021",
+                "--emit=metadata",
+                "--color=never",
+                "-A",
+                "warnings",
+            ])
+            .arg(&file)
+            .current_dir(&dir)
+            .output();
+        fs::remove_dir_all(&dir).ok();
+        let output = output.map_err(|e| format!("could not run {rustc}: {e}"))?;
+        if output.status.success() {
+            return Err(format!("{} compiled, but it should not have", self.name));
+        }
+        Ok(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+
+    pub fn explain(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} (from {}, error {})",
+            self.name, self.module, self.error
+        )?;
+        writeln!(out, "{}\n", self.explanation)?;
+        writeln!(out, "{}", self.source())?;
+        match self.compile() {
+            Ok(diagnostic) => write!(out, "rustc says:\n\n{diagnostic}"),
+            Err(e) => writeln!(out, "{e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod compile_fail_tests {
+    use super::*;
+    use crate::examples::REGISTRY;
+
+    #[test]
+    fn every_snippet_still_fails_to_compile() {
+        for snippet in CATALOG {
+            assert!(REGISTRY
+                .iter()
+                .any(|example| example.name() == snippet.module));
+            let diagnostic = snippet.compile().unwrap();
+            assert!(
+                diagnostic.contains(&format!("error[{}]", snippet.error)),
+                "{} did not fail with {}:\n{diagnostic}",
+                snippet.name,
+                snippet.error
+            );
+        }
+    }
+}
@@ -1,11 +1,43 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::{quiz::Question, Example};
+
+pub struct ControlFlow;
+
+impl Example for ControlFlow {
+    fn name(&self) -> &'static str {
+        "control_flow"
+    }
+
+    fn chapter(&self) -> u32 {
+        3
+    }
+
+    fn description(&self) -> &'static str {
+        "if expressions, loops with labels and break values, while and for"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        control_flow(out)?;
+        Ok(())
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+}
+
 #[allow(unused)]
-pub fn control_flow() {
+pub fn control_flow(out: &mut dyn Write) -> io::Result<()> {
     // if statements
     let number = 3;
     if number < 5 {
-        println!("condition was true");
+        writeln!(out, "condition was true")?;
     } else {
-        println!("condition was false");
+        writeln!(out, "condition was false")?;
     }
 
     // you cannot use values as booleans unless they are specifically bool
@@ -14,30 +46,32 @@ pub fn control_flow() {
     // if number {
     //     println!("number was three");
     // }
+    // see `examples explain-error number-as-condition`
 
     // using else if
     // important note: Rust only finds the first block which is true, and then ignores the rest
     // so multiple conditions can be true, but only the first will run
     let number = 6;
     if number % 4 == 0 {
-        println!("number is divisible by 4");
+        writeln!(out, "number is divisible by 4")?;
     } else if number % 3 == 0 {
-        println!("number is divisible by 3");
+        writeln!(out, "number is divisible by 3")?;
     } else if number % 2 == 0 {
-        println!("number is divisible by 2");
+        writeln!(out, "number is divisible by 2")?;
     } else {
-        println!("number is not divisible by 4, 3, or 2");
+        writeln!(out, "number is not divisible by 4, 3, or 2")?;
     }
 
     // using if in a let statement
     let condition = true;
     let number = if condition { 5 } else { 6 };
-    println!("The value of number is: {number}");
+    writeln!(out, "The value of number is: {number}")?;
 
     // both outcomes must be of the same type, otherwise the compiller will error
     // let condition = true;
     // let number = if condition { 5 } else { "six" };
     // println!("The value of number is: {number}");
+    // see `examples explain-error if-arms-differ`
 
     // returning values from loops
     let mut counter = 0;
@@ -47,15 +81,15 @@ pub fn control_flow() {
             break counter * 2; // value is returned here (can omit semicolon or include)
         }
     };
-    println!("The result is {result}");
+    writeln!(out, "The result is {result}")?;
 
     // loops within loops
     let mut count = 0;
     'counting_up: loop {
-        println!("count = {count}");
+        writeln!(out, "count = {count}")?;
         let mut remaining = 10;
         loop {
-            println!("remaining = {remaining}");
+            writeln!(out, "remaining = {remaining}")?;
             if remaining == 9 {
                 break;
             }
@@ -66,25 +100,91 @@ pub fn control_flow() {
         }
         count += 1;
     }
-    println!("end count = {count}");
+    writeln!(out, "end count = {count}")?;
 
     // counting down with a while loop
     let mut number = 3000;
     while number != 0 {
-        println!("{number}");
+        writeln!(out, "{number}")?;
         number -= 1;
     }
-    println!("LIFTOFF!");
+    writeln!(out, "LIFTOFF!")?;
 
     // looping through a collection with for
     let a = [10, 20, 30, 40, 50];
     for element in a {
-        println!("the value is: {element}");
+        writeln!(out, "the value is: {element}")?;
     }
 
     // counting down using a range
     for number in (1..1000).rev() {
-        println!("{number}");
+        writeln!(out, "{number}")?;
+    }
+    writeln!(out, "LIIFTOFF.. AGAIN!!!")?;
+    Ok(())
+}
+
+pub const QUIZ: &[Question] = &[
+    Question {
+        code: r#"
+let mut counter = 0;
+let result = loop {
+    counter += 1;
+    if counter == 10 {
+        break counter * 2;
     }
-    println!("LIIFTOFF.. AGAIN!!!");
+};
+println!("The result is {result}");
+"#,
+        run: |out| {
+            let mut counter = 0;
+            let result = loop {
+                counter += 1;
+                if counter == 10 {
+                    break counter * 2;
+                }
+            };
+            writeln!(out, "The result is {result}")
+        },
+    },
+    Question {
+        code: r#"
+let mut count = 0;
+'counting_up: loop {
+    println!("count = {count}");
+    let mut remaining = 10;
+    loop {
+        println!("remaining = {remaining}");
+        if remaining == 9 {
+            break;
+        }
+        if count == 2 {
+            break 'counting_up;
+        }
+        remaining -= 1;
+    }
+    count += 1;
 }
+println!("end count = {count}");
+"#,
+        run: |out| {
+            let mut count = 0;
+            'counting_up: loop {
+                writeln!(out, "count = {count}")?;
+                let mut remaining = 10;
+                loop {
+                    writeln!(out, "remaining = {remaining}")?;
+                    if remaining == 9 {
+                        break;
+                    }
+                    if count == 2 {
+                        break 'counting_up;
+                    }
+                    remaining -= 1;
+                }
+                count += 1;
+            }
+            writeln!(out, "end count = {count}")
+        },
+    },
+];
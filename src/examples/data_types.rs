@@ -1,7 +1,37 @@
-use std::io;
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::{prompt::index_prompt, quiz::Question, Example};
+
+pub struct DataTypes;
+
+impl Example for DataTypes {
+    fn name(&self) -> &'static str {
+        "data_types"
+    }
+
+    fn chapter(&self) -> u32 {
+        3
+    }
+
+    fn description(&self) -> &'static str {
+        "scalar and compound types, and checking an index before using it"
+    }
+
+    fn run(&self, out: &mut dyn Write, input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        data_types(out, input)?;
+        Ok(())
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+}
 
 #[allow(unused)]
-pub fn data_types() {
+pub fn data_types(out: &mut dyn Write, input: &mut dyn BufRead) -> io::Result<()> {
     // unused variables can be preceeded by "_" to prevent compiler from complaining
 
     // if we do not put a type here, the compiler will ask for one
@@ -39,15 +69,16 @@ pub fn data_types() {
 
     // destruturing a tuple (extracting the values into their own variables)
     let (x, y, z) = tup;
-    println!("The tuple contains: {x}, {y}, and {z}");
+    writeln!(out, "The tuple contains: {x}, {y}, and {z}")?;
 
     // accessing a tuple with dot notation
-    println!(
+    writeln!(
+        out,
         "Here is how to access the tuple using dot notation: {}, {}, and {}",
         { tup.0 },
         { tup.1 },
         { tup.2 }
-    );
+    )?;
 
     // "the unit"
     // if a function or expression does not return a value, it automatically returns a unit type instead
@@ -58,7 +89,7 @@ pub fn data_types() {
 
     // accessing arrays
     let first = a[0];
-    println!("{first}");
+    writeln!(out, "{first}")?;
 
     // arrays are useful when we know our data won't change
     let _months: [&str; 12] = [
@@ -79,16 +110,38 @@ pub fn data_types() {
     // shorthand init for arrays
     let arr: [i32; 5] = [3; 5]; // [3, 3, 3, 3, 3]
 
-    // trying to access an array index which doesn't exist will cause a panic
-    println!("Please enter an array index.");
-    let mut index = String::new();
-    io::stdin()
-        .read_line(&mut index)
-        .expect("Failed to read line");
-    let index: usize = index
-        .trim()
-        .parse()
-        .expect("Index entered was not a number");
+    // trying to access an array index which doesn't exist will cause a panic,
+    // so the prompt only takes an index the array has, and asks again for anything else
+    let index = index_prompt("Please enter an array index.", &arr)
+        .tries(3)
+        .ask(out, input)?;
     let element = arr[index];
-    println!("The value of the element at index {index} is: {element}");
+    writeln!(
+        out,
+        "The value of the element at index {index} is: {element}"
+    )?;
+    Ok(())
 }
+
+pub const QUIZ: &[Question] = &[
+    Question {
+        code: r#"
+let truncated = -5 / 3;
+println!("{truncated}");
+"#,
+        run: |out| {
+            let truncated = -5 / 3;
+            writeln!(out, "{truncated}")
+        },
+    },
+    Question {
+        code: r#"
+let remainder = 43 % 5;
+println!("{remainder}");
+"#,
+        run: |out| {
+            let remainder = 43 % 5;
+            writeln!(out, "{remainder}")
+        },
+    },
+];
@@ -1,5 +1,33 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::{guess::Guess, Example};
+
+pub struct Enums;
+
+impl Example for Enums {
+    fn name(&self) -> &'static str {
+        "enums"
+    }
+
+    fn chapter(&self) -> u32 {
+        6
+    }
+
+    fn description(&self) -> &'static str {
+        "enums with data, Option, match and if let"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        enums(out)?;
+        Ok(())
+    }
+}
+
 #[allow(unused)]
-pub fn enums() {
+pub fn enums(out: &mut dyn Write) -> io::Result<()> {
     // creating an enum
     // each enum can have variants
     // each variant can take in different types of data
@@ -24,12 +52,12 @@ pub fn enums() {
         ChangeColor(i32, i32, i32),
     }
     impl Message {
-        fn call(&self) {
-            println!("Calling!")
+        fn call(&self, out: &mut dyn Write) -> io::Result<()> {
+            writeln!(out, "Calling!")
         }
     }
     let m = Message::Write(String::from("hello"));
-    m.call();
+    m.call(out)?;
 
     // the option type
     // can be in two starts, either it has Some value or it has None
@@ -42,6 +70,7 @@ pub fn enums() {
     // let x: i8 = 5;
     // let y: Option<i8> = Some(5);
     // let sum = x + y;
+    // see `examples explain-error option-plus-number`
 
     // using match with enums to derive values
     enum Coin {
@@ -50,25 +79,26 @@ pub fn enums() {
         Dime,
         Quarter(UsState),
     }
-    fn value_in_cents(coin: Coin) -> u8 {
+    fn value_in_cents(out: &mut dyn Write, coin: Coin) -> io::Result<u8> {
         // the result of the match statement is returned
-        match coin {
+        // it is wrapped in Ok because writing to out can fail
+        Ok(match coin {
             // if an arm has more than a simple return, use curly braces
             Coin::Penny => {
-                println!("lucky penny");
+                writeln!(out, "lucky penny")?;
                 1
             }
             Coin::Nickle => 5,
             Coin::Dime => 10,
             Coin::Quarter(state) => {
-                println!("This quarter is from {:?}!", state);
+                writeln!(out, "This quarter is from {:?}!", state)?;
                 25
             }
-        }
+        })
     }
     let penny = Coin::Penny;
-    let cents: u8 = value_in_cents(penny);
-    println!("the coin is worth {cents} cents");
+    let cents: u8 = value_in_cents(out, penny)?;
+    writeln!(out, "the coin is worth {cents} cents")?;
 
     // combining enums
     #[derive(Debug)]
@@ -78,8 +108,8 @@ pub fn enums() {
         // ect..
     }
     let quarter = Coin::Quarter(UsState::Alabama);
-    let which_quarter = value_in_cents(quarter);
-    println!("{:?}", which_quarter);
+    let which_quarter = value_in_cents(out, quarter)?;
+    writeln!(out, "{:?}", which_quarter)?;
 
     // running match statements with Option<T>
     fn plus_one(x: Option<i32>) -> Option<i32> {
@@ -93,10 +123,13 @@ pub fn enums() {
     let five = Some(5);
     let six = plus_one(five);
     let none = plus_one(None);
-    println!("{:?} {:?} {:?}", five, six, none);
+    writeln!(out, "{:?} {:?} {:?}", five, six, none)?;
 
     // here, we can target specific values in a match, and have general functionality for all others
-    let dice_roll = 9;
+    // a roll of two dice is from 2 to 12, a Guess from guess.rs makes sure of that
+    let dice_roll = Guess::new(9, 2..=12)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        .value();
     match dice_roll {
         3 => add_fancy_hat(),
         7 => remove_fancy_hat(),
@@ -127,22 +160,23 @@ pub fn enums() {
     //here is an example where we do this the verbose way
     let config_max = Some(3u8);
     match config_max {
-        Some(max) => println!("the max is configured to be {}", max),
+        Some(max) => writeln!(out, "the max is configured to be {}", max)?,
         _ => (), // do nothing if we do not have some value
     }
 
     // not we can use if let to make this more consise
     let config_max = Some(3u8);
     if let Some(max) = config_max {
-        println!("the max config is {}", max)
+        writeln!(out, "the max config is {}", max)?
     }
 
     // using else in if let
     let mut _count = 0;
     let coin = Coin::Quarter(UsState::Alabama);
     if let Coin::Quarter(state) = coin {
-        println!("state quarter from {:?}", state)
+        writeln!(out, "state quarter from {:?}", state)?
     } else {
         _count += 1;
     }
+    Ok(())
 }
@@ -1,11 +1,39 @@
 use std::io::Read;
 use std::{
+    error::Error,
     fs::File,
-    io::{self, ErrorKind},
+    io::{self, BufRead, ErrorKind, Write},
 };
 
+use super::Example;
+
+pub struct Errors;
+
+impl Example for Errors {
+    fn name(&self) -> &'static str {
+        "errors"
+    }
+
+    fn chapter(&self) -> u32 {
+        9
+    }
+
+    fn description(&self) -> &'static str {
+        "panic!, Result, unwrap, expect and the ? operator"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        errors(out)?;
+        Ok(())
+    }
+
+    fn writes_files(&self) -> &'static [&'static str] {
+        &["hello.txt"]
+    }
+}
+
 #[allow(unused)]
-pub fn errors() {
+pub fn errors(out: &mut dyn Write) -> io::Result<()> {
     // manually make the program crash
     // panic!("crash and burn");
 
@@ -39,6 +67,12 @@ pub fn errors() {
         },
     };
 
+    writeln!(
+        out,
+        "hello.txt is open, it has {} bytes",
+        greeting_file.metadata()?.len()
+    )?;
+
     // using unwrap to shortcut error when we want a default panic
     // if the file is not found, a panic will be called
     let greeting_file = File::open("hello.txt").unwrap();
@@ -61,7 +95,8 @@ pub fn errors() {
         }
     }
 
-    let _function_result = read_username_from_file().unwrap();
+    let username = read_username_from_file().unwrap();
+    writeln!(out, "the username in hello.txt is {username:?}")?;
 
     // shorthand syntax for propagating errors using "?"
     fn read_username_from_another_file() -> Result<String, io::Error> {
@@ -69,9 +104,11 @@ pub fn errors() {
         File::open("hello.txt")?.read_to_string(&mut username)?;
         Ok(username)
     }
-    let _function_result: String = read_username_from_another_file().unwrap();
+    let username: String = read_username_from_another_file().unwrap();
+    writeln!(out, "read with ? it is {username:?} too")?;
 
     // the ? operator can only be used in function which have a return type that matches the potential error type
     // the following code fails to run because we have defined the return type
     // let greeting_file = File::open("hello.txt")?;
+    Ok(())
 }
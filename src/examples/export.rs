@@ -0,0 +1,352 @@
+// turns the examples into study pages, one per example plus a table of contents by chapter
+// a page is the source of the example with its comments pulled out as prose between the code,
+// followed by what the example printed when it was run for the export
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{
+    batch::{run_batch, Status},
+    chapter_title,
+    script::Script,
+    sources::{files_of, prose_comment},
+    Example,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            other => Err(format!(
+                "unknown export format '{other}', expected md or html"
+            )),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Prose(String),
+    Code(String),
+}
+
+// splits the source into comments and the code between them
+// whole line comments become prose, comments behind code stay where they are
+fn blocks(source: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut code: Vec<&str> = Vec::new();
+    let mut prose: Vec<&str> = Vec::new();
+
+    fn flush_code(code: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+        while code.last().is_some_and(|line| line.trim().is_empty()) {
+            code.pop();
+        }
+        let start = code
+            .iter()
+            .take_while(|line| line.trim().is_empty())
+            .count();
+        if start < code.len() {
+            blocks.push(Block::Code(code[start..].join("\n")));
+        }
+        code.clear();
+    }
+    fn flush_prose(prose: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+        if !prose.is_empty() {
+            blocks.push(Block::Prose(prose.join(" ")));
+            prose.clear();
+        }
+    }
+
+    for line in source.lines() {
+        match prose_comment(line) {
+            Some(comment) => {
+                flush_code(&mut code, &mut blocks);
+                if !comment.is_empty() {
+                    prose.push(comment);
+                }
+            }
+            _ => {
+                flush_prose(&mut prose, &mut blocks);
+                code.push(line);
+            }
+        }
+    }
+    flush_code(&mut code, &mut blocks);
+    flush_prose(&mut prose, &mut blocks);
+    blocks
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// the title is expected to be escaped already
+fn html_start(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    )
+}
+
+fn page_name(example: &dyn Example, format: Format) -> String {
+    format!("{}.{}", example.name(), format.extension())
+}
+
+// what running the example printed, or why it stopped early
+fn captured_output(example: &dyn Example, script: &mut Script) -> (String, Option<String>) {
+    // an export should not leave files behind wherever it was started
+    if let files @ [_, ..] = example.writes_files() {
+        let files = files.join(", ");
+        let problem = format!("the example was not run for the export, it creates {files}");
+        return (String::new(), Some(problem));
+    }
+    let mut out = Vec::new();
+    let batch = run_batch(&[example], &mut out, script);
+    let problem = match &batch.outcomes[0].status {
+        Status::Passed => None,
+        Status::Panicked(message) => Some(format!("the example panicked: {message}")),
+        Status::Failed(message) => Some(format!("the example stopped with an error: {message}")),
+    };
+    (String::from_utf8_lossy(&out).into_owned(), problem)
+}
+
+fn render_page(example: &dyn Example, format: Format, script: &mut Script) -> String {
+    let (output, problem) = captured_output(example, script);
+    let chapter = format!(
+        "chapter {}: {}",
+        example.chapter(),
+        chapter_title(example.chapter())
+    );
+    let index = format!("index.{}", format.extension());
+    let mut page = String::new();
+    match format {
+        Format::Markdown => {
+            page += &format!("# {}\n\n", example.name());
+            page += &format!("{chapter}, {}\n\n", example.description());
+            page += &format!("[back to the contents]({index})\n\n## Source\n\n");
+            for file in files_of(example.name()) {
+                page += &format!("### {}\n\n", file.path);
+                for block in blocks(file.text) {
+                    match block {
+                        Block::Prose(text) => page += &format!("{text}\n\n"),
+                        Block::Code(code) => page += &format!("```rust\n{code}\n```\n\n"),
+                    }
+                }
+            }
+            page += "## Output\n\n";
+            let newline = if output.is_empty() || output.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            page += &format!("```text\n{output}{newline}```\n");
+            if let Some(problem) = problem {
+                page += &format!("\n{problem}\n");
+            }
+        }
+        Format::Html => {
+            let name = escape_html(example.name());
+            page += &html_start(&name);
+            page += &format!(
+                "<p>{}, {}</p>\n",
+                escape_html(&chapter),
+                escape_html(example.description())
+            );
+            page +=
+                &format!("<p><a href=\"{index}\">back to the contents</a></p>\n<h2>Source</h2>\n");
+            for file in files_of(example.name()) {
+                page += &format!("<h3>{}</h3>\n", escape_html(file.path));
+                for block in blocks(file.text) {
+                    match block {
+                        Block::Prose(text) => page += &format!("<p>{}</p>\n", escape_html(&text)),
+                        Block::Code(code) => {
+                            page += &format!("<pre><code>{}</code></pre>\n", escape_html(&code))
+                        }
+                    }
+                }
+            }
+            page += &format!(
+                "<h2>Output</h2>\n<pre><samp>{}</samp></pre>\n",
+                escape_html(&output)
+            );
+            if let Some(problem) = problem {
+                page += &format!("<p>{}</p>\n", escape_html(&problem));
+            }
+            page += "</body>\n</html>\n";
+        }
+    }
+    page
+}
+
+fn render_index(examples: &[&dyn Example], format: Format) -> String {
+    let mut page = String::new();
+    if format == Format::Html {
+        page += &html_start("Rust book examples");
+    } else {
+        page += "# Rust book examples\n";
+    }
+    let mut chapter = 0;
+    for example in examples {
+        if example.chapter() != chapter {
+            if chapter != 0 && format == Format::Html {
+                page += "</ul>\n";
+            }
+            chapter = example.chapter();
+            let title = format!("Chapter {chapter}: {}", chapter_title(chapter));
+            match format {
+                Format::Markdown => page += &format!("\n## {title}\n\n"),
+                Format::Html => page += &format!("<h2>{}</h2>\n<ul>\n", escape_html(&title)),
+            }
+        }
+        let link = page_name(*example, format);
+        match format {
+            Format::Markdown => {
+                page += &format!(
+                    "- [{}]({link}): {}\n",
+                    example.name(),
+                    example.description()
+                )
+            }
+            Format::Html => {
+                page += &format!(
+                    "<li><a href=\"{link}\">{}</a>: {}</li>\n",
+                    escape_html(example.name()),
+                    escape_html(example.description())
+                )
+            }
+        }
+    }
+    if format == Format::Html {
+        if chapter != 0 {
+            page += "</ul>\n";
+        }
+        page += "</body>\n</html>\n";
+    }
+    page
+}
+
+// writes a page for every example and the index into dir, and gives back the files it wrote
+// the examples that ask for input get the answers of the script, like in a batch run
+pub fn export(
+    examples: &[&dyn Example],
+    format: Format,
+    dir: &Path,
+    script: &mut Script,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for example in examples {
+        let path = dir.join(page_name(*example, format));
+        fs::write(&path, render_page(*example, format, script))?;
+        written.push(path);
+    }
+    let path = dir.join(format!("index.{}", format.extension()));
+    fs::write(&path, render_index(examples, format))?;
+    written.push(path);
+    Ok(written)
+}
+
+pub fn report(written: &[PathBuf], out: &mut dyn Write) -> io::Result<()> {
+    for path in written {
+        writeln!(out, "wrote {}", path.display())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+    use crate::examples::{data_types::DataTypes, errors::Errors, hello_world::HelloWorld};
+    use std::{error::Error, io::BufRead};
+
+    struct NoNewline;
+
+    impl Example for NoNewline {
+        fn name(&self) -> &'static str {
+            "no_newline"
+        }
+
+        fn chapter(&self) -> u32 {
+            1
+        }
+
+        fn description(&self) -> &'static str {
+            "prints without a newline at the end"
+        }
+
+        fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+            write!(out, "no newline")?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn comments_become_prose() {
+        let source = "\
+// printing
+fn main() {
+    // a comment in the middle
+    // that goes on
+    println!(\"hi\"); // stays
+    // let x = 5;
+}
+";
+        assert_eq!(
+            blocks(source),
+            vec![
+                Block::Prose("printing".to_string()),
+                Block::Code("fn main() {".to_string()),
+                Block::Prose("a comment in the middle that goes on".to_string()),
+                Block::Code("    println!(\"hi\"); // stays\n    // let x = 5;\n}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pages_link_back_to_the_index() {
+        let examples: &[&dyn Example] = &[&HelloWorld, &DataTypes];
+        let page = render_page(&HelloWorld, Format::Markdown, &mut Script::new());
+        assert!(page.contains("[back to the contents](index.md)"));
+        assert!(page.contains("```text\nhello world\n```"));
+
+        let page = render_page(&DataTypes, Format::Html, &mut Script::new());
+        assert!(page.contains(
+            "the example stopped with an error: the input ended before an answer was given"
+        ));
+        assert!(page.contains("let a: [i32; 5] = [1, 2, 3, 4, 5];"));
+
+        let page = render_page(&NoNewline, Format::Markdown, &mut Script::new());
+        assert!(page.ends_with("```text\nno newline\n```\n"));
+
+        let page = render_page(&Errors, Format::Markdown, &mut Script::new());
+        assert!(page.contains("the example was not run for the export, it creates hello.txt"));
+
+        let index = render_index(examples, Format::Html);
+        assert!(index.contains(
+            "<h2>Chapter 1: Getting Started</h2>\n<ul>\n<li><a href=\"hello_world.html\">"
+        ));
+        assert_eq!(
+            index.matches("<ul>").count(),
+            index.matches("</ul>").count()
+        );
+    }
+}
@@ -1,12 +1,44 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::{quiz::Question, Example};
+
+pub struct Functions;
+
+impl Example for Functions {
+    fn name(&self) -> &'static str {
+        "functions"
+    }
+
+    fn chapter(&self) -> u32 {
+        3
+    }
+
+    fn description(&self) -> &'static str {
+        "parameters, statements and expressions, and return values"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        functions(out)?;
+        Ok(())
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+}
 
 #[allow(unused)]
-pub fn functions() {
-    fn another_function(x: i32, unit_label: char) {
-        println!("another function!");
-        println!("The value of x is: {x}{unit_label}");
+pub fn functions(out: &mut dyn Write) -> io::Result<()> {
+    // out is where the example writes its output, so functions that print take it as a parameter
+    fn another_function(out: &mut dyn Write, x: i32, unit_label: char) -> io::Result<()> {
+        writeln!(out, "another function!")?;
+        writeln!(out, "The value of x is: {x}{unit_label}")
     }
 
-    another_function(10, 'h');
+    another_function(out, 10, 'h')?;
 
     // Statements are instructions that perform some action and do not return a value.
     // Expressions evaluate to a resultant value. Let’s look at some examples.
@@ -16,14 +48,14 @@ pub fn functions() {
         let x = 3;
         x + 1 // this value does not include a semicolon because it is returned from the experssion
     };
-    println!("The value of y is: {y}");
+    writeln!(out, "The value of y is: {y}")?;
 
     // return values in functions
     fn five() -> i32 {
         5
     }
     let x = five();
-    println!("The value of x is: {x}");
+    writeln!(out, "The value of x is: {x}")?;
 
     fn plus_one(x: i32) -> i32 {
         x + 1
@@ -31,5 +63,23 @@ pub fn functions() {
 
     // shadowing x
     let x = plus_one(2);
-    println!("The value of x is: {x}");
+    writeln!(out, "The value of x is: {x}")?;
+    Ok(())
 }
+
+pub const QUIZ: &[Question] = &[Question {
+    code: r#"
+let y = {
+    let x = 3;
+    x + 1
+};
+println!("The value of y is: {y}");
+"#,
+    run: |out| {
+        let y = {
+            let x = 3;
+            x + 1
+        };
+        writeln!(out, "The value of y is: {y}")
+    },
+}];
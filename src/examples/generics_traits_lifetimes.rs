@@ -1,7 +1,34 @@
-use std::fmt::{Debug, Display};
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct GenericsTraitsLifetimes;
+
+impl Example for GenericsTraitsLifetimes {
+    fn name(&self) -> &'static str {
+        "generics_traits_lifetimes"
+    }
+
+    fn chapter(&self) -> u32 {
+        10
+    }
+
+    fn description(&self) -> &'static str {
+        "generic types, traits as shared behavior and lifetimes"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        generic_traits_lifetimes(out)?;
+        Ok(())
+    }
+}
 
 #[allow(unused)]
-pub fn generic_traits_lifetimes() {
+pub fn generic_traits_lifetimes(out: &mut dyn Write) -> io::Result<()> {
     // using generics in a struct definition
     struct Point<T> {
         x: T,
@@ -14,6 +41,7 @@ pub fn generic_traits_lifetimes() {
 
     // but you cannot mix types with our scruct because it only has one type T
     // let wont_work = Point { x: 5, y: 1.2 };
+    // see `examples explain-error mixed-point-types`
 
     // if we include multiple types in the definition, we can blend types
     struct AnotherPoint<T, U> {
@@ -34,7 +62,7 @@ pub fn generic_traits_lifetimes() {
     }
 
     // now we can use the method here
-    println!("p.x = {}", integer.x());
+    writeln!(out, "p.x = {}", integer.x())?;
 
     // now lets write a method which only applies to Points built with floats
     // notice we do not need any <T> after impl because we specificed a concrete type
@@ -45,10 +73,11 @@ pub fn generic_traits_lifetimes() {
     }
 
     // lets use the method for floats on a float
-    println!(
+    writeln!(
+        out,
         "the distance from the origin for our float is: {}",
         float.distance_from_origin()
-    );
+    )?;
 
     // but if we try to apply this to a Point build with integers, it will cause a panic
     // the following method call will fail
@@ -76,7 +105,7 @@ pub fn generic_traits_lifetimes() {
     let p1 = MorePoints { x: 5, y: 10.6 };
     let p2 = MorePoints { x: "hello", y: 'x' };
     let p3 = p1.mixup(p2);
-    println!("p3.x = {}, p3.y = {}", p3.x, p3.y);
+    writeln!(out, "p3.x = {}, p3.y = {}", p3.x, p3.y)?;
 
     // defining shared behavior using traits
     // lets create a trait called Summary that has a method called summarize
@@ -149,7 +178,7 @@ pub fn generic_traits_lifetimes() {
         reply: false,
         retweet: false,
     };
-    println!("1 new tweet: {}", tweet.summarize());
+    writeln!(out, "1 new tweet: {}", tweet.summarize())?;
 
     // lets create a type where we do not define summarize, then we can use the default implementation
     pub struct Post {
@@ -169,24 +198,31 @@ pub fn generic_traits_lifetimes() {
         author: String::from("Bill"),
         content: String::from("I was walking and then.."),
     };
-    println!("Using posts default summarize method: {}", post.summarize());
-    println!(
+    writeln!(
+        out,
+        "Using posts default summarize method: {}",
+        post.summarize()
+    )?;
+    writeln!(
+        out,
         "Using posts default summarize_reliant_on_author method: {}",
         post.summarize_reliant_on_author()
-    );
+    )?;
 
     // we can also use traits as parameters for functions
     // basically, any type with the trait can be passed to the function
     // notice how we are taking a reference to the type with the trait
     // this means when we call the function we must pass a reference of our type which contains the trait
-    pub fn notify(item: &impl Summary) {
-        println!(
+    // out is passed in first so the function can write its output
+    pub fn notify(out: &mut dyn Write, item: &impl Summary) -> io::Result<()> {
+        writeln!(
+            out,
             "Printing from a function which takes a trait as a param! Result = {}",
             item.summarize()
-        );
+        )
     }
     // here we use the method, and we are sure to pass the reference to tweet
-    notify(&tweet);
+    notify(out, &tweet)?;
 
     // above, we used the impl Trait syntax, but you can also use the trait bound syntax
     // lets rewrite the function call above to use trait bound syntax
@@ -194,11 +230,11 @@ pub fn generic_traits_lifetimes() {
     // using the syntax in notify, we can pass multiple params which implement Summary, but we have no
     // was of ensuring they are of the same type
     // using trait bound syntax, we can be sure item1 and item2 are always the same type
-    pub fn notify_twice<T: Summary>(item1: &T, item2: &T) {
-        println!("{}", item1.summarize());
-        println!("{}", item2.summarize());
+    pub fn notify_twice<T: Summary>(out: &mut dyn Write, item1: &T, item2: &T) -> io::Result<()> {
+        writeln!(out, "{}", item1.summarize())?;
+        writeln!(out, "{}", item2.summarize())
     }
-    notify_twice(&tweet, &tweet);
+    notify_twice(out, &tweet, &tweet)?;
 
     // using multiple trait bounds using the "+" syntax
     // this method only accepts a type which implements Summary and Display traits
@@ -285,11 +321,11 @@ pub fn generic_traits_lifetimes() {
     // now we implement a method which has a restriction
     // these methods can be called only if the created type has implemented Display and PartialOrd
     impl<T: Display + PartialOrd> Pair<T> {
-        fn cmp_display(&self) {
+        fn cmp_display(&self, out: &mut dyn Write) -> io::Result<()> {
             if self.x >= self.y {
-                println!("the largest member is x = {}", self.x);
+                writeln!(out, "the largest member is x = {}", self.x)
             } else {
-                println!("the largest member is y = {}", self.y);
+                writeln!(out, "the largest member is y = {}", self.y)
             }
         }
     }
@@ -309,7 +345,7 @@ pub fn generic_traits_lifetimes() {
     let string1 = String::from("abcd");
     let string2 = "xyz";
     let result = longest(string1.as_str(), string2);
-    println!("the longest string is {}", result);
+    writeln!(out, "the longest string is {}", result)?;
 
     // if we implements the longest function as follows, we will get an error about lifetimes
     // we have no idea if x or y will be returned from the function
@@ -322,6 +358,7 @@ pub fn generic_traits_lifetimes() {
     //         y
     //     }
     // }
+    // see `examples explain-error longest-without-lifetimes`
 
     // lets rewrite the function using lifetimes
     // now the function will work!
@@ -350,6 +387,7 @@ pub fn generic_traits_lifetimes() {
     // so the borrow checker will complain and let us know s2 dies before we try and use result
     // these constaints are derived from the lifetimes we specified in the longest function definition
     // println!("the longest string is {}", result);
+    // see `examples explain-error does-not-live-long-enough`
 
     // we can also use reference in struct definitions, but to do so, we must speficy the lifetimes
     struct ImportantExcerpt<'a> {
@@ -397,27 +435,38 @@ pub fn generic_traits_lifetimes() {
             3
         }
         // here is an example of where we can omit the lifetime of the return value because of rule 3
-        fn announce_and_return_part(&self, announcement: &str) -> &str {
-            println!("attention please: {}", announcement);
-            self.part
+        // (out does not change that, it gets its own lifetime like announcement does)
+        fn announce_and_return_part(
+            &self,
+            out: &mut dyn Write,
+            announcement: &str,
+        ) -> io::Result<&str> {
+            writeln!(out, "attention please: {}", announcement)?;
+            Ok(self.part)
         }
     }
 
     // lets discuss static lifetimes
     // static lifetimes are stored directly in a programs binary and are always availble
     let static_string: &'static str = "I have a static lifetime";
-    println!("{static_string}");
+    writeln!(out, "{static_string}")?;
 
     // an example of a function which uses trait bounds, lifetimes, and generics all in one
-    fn longest_with_an_announcement<'a, T>(x: &'a str, y: &'a str, ann: T) -> &'a str
+    fn longest_with_an_announcement<'a, T>(
+        out: &mut dyn Write,
+        x: &'a str,
+        y: &'a str,
+        ann: T,
+    ) -> io::Result<&'a str>
     where
         T: Display,
     {
-        println!("Announcement! {}", ann);
+        writeln!(out, "Announcement! {}", ann)?;
         if x.len() > y.len() {
-            x
+            Ok(x)
         } else {
-            y
+            Ok(y)
         }
     }
+    Ok(())
 }
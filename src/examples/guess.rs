@@ -0,0 +1,151 @@
+// a number that is known to be in range, the way chapter 9 suggests instead of checking it everywhere
+// tests.rs has the Guess from the book, which panics when the value is out of range,
+// this one hands back an error that says what was wrong, so a program can ask again instead
+
+use std::{
+    error::Error,
+    fmt,
+    num::{IntErrorKind, ParseIntError},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Guess<T> {
+    value: T,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuessError<T> {
+    Empty,
+    // the text that could not be read as a number
+    NotANumber(String),
+    // the value as it was typed, a number too big for T is still out of range
+    OutOfRange {
+        value: String,
+        below: bool,
+        range: RangeInclusive<T>,
+    },
+}
+
+impl<T: PartialOrd + Copy + fmt::Display> Guess<T> {
+    pub fn new(value: T, range: RangeInclusive<T>) -> Result<Guess<T>, GuessError<T>> {
+        if !range.contains(&value) {
+            return Err(GuessError::OutOfRange {
+                value: value.to_string(),
+                below: value < *range.start(),
+                range,
+            });
+        }
+        Ok(Guess { value })
+    }
+
+    // the spaces and the newline around a line of input are ignored
+    pub fn parse(text: &str, range: RangeInclusive<T>) -> Result<Guess<T>, GuessError<T>>
+    where
+        T: FromStr<Err = ParseIntError>,
+    {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let out_of_range = |below| GuessError::OutOfRange {
+            value: text.to_string(),
+            below,
+            range: range.clone(),
+        };
+        let value = text.parse().map_err(|e: ParseIntError| match e.kind() {
+            IntErrorKind::PosOverflow => out_of_range(false),
+            IntErrorKind::NegOverflow => out_of_range(true),
+            // an unsigned number can not start with a minus, but -3 is still a number below the range
+            IntErrorKind::InvalidDigit
+                if text.strip_prefix('-').is_some_and(|digits| {
+                    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+                }) =>
+            {
+                out_of_range(true)
+            }
+            _ => GuessError::NotANumber(text.to_string()),
+        })?;
+        Guess::new(value, range)
+    }
+
+    pub fn value(self) -> T {
+        self.value
+    }
+}
+
+// from 1 to 100, like the Guess in the book
+impl<T: PartialOrd + Copy + fmt::Display + FromStr<Err = ParseIntError> + From<u8>> TryFrom<&str>
+    for Guess<T>
+{
+    type Error = GuessError<T>;
+
+    fn try_from(text: &str) -> Result<Guess<T>, GuessError<T>> {
+        Guess::parse(text, T::from(1)..=T::from(100))
+    }
+}
+
+impl<T: fmt::Display + PartialOrd> fmt::Display for GuessError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "nothing was typed, a number is needed"),
+            GuessError::NotANumber(text) => write!(f, "'{text}' is not a whole number"),
+            GuessError::OutOfRange {
+                value,
+                below,
+                range,
+            } => {
+                let (start, end) = (range.start(), range.end());
+                let side = if *below { "below" } else { "above" };
+                write!(
+                    f,
+                    "{value} is {side} the range, it has to be from {start} to {end}"
+                )
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display + PartialOrd> Error for GuessError<T> {}
+
+#[cfg(test)]
+mod guess_tests {
+    use super::*;
+
+    #[test]
+    fn errors_say_why_the_input_was_rejected() {
+        assert_eq!(Guess::try_from("42"), Ok(Guess { value: 42u32 }));
+        let error = |text: &str| Guess::<i32>::try_from(text).unwrap_err().to_string();
+        assert_eq!(error(""), "nothing was typed, a number is needed");
+        assert_eq!(error("4 2"), "'4 2' is not a whole number");
+        assert_eq!(
+            error("200\n"),
+            "200 is above the range, it has to be from 1 to 100"
+        );
+        assert_eq!(
+            error("-3"),
+            "-3 is below the range, it has to be from 1 to 100"
+        );
+        // a u8 can not hold 300 at all, but it is still above the range
+        assert_eq!(
+            Guess::<u8>::parse("300", 0..=9).unwrap_err().to_string(),
+            "300 is above the range, it has to be from 0 to 9"
+        );
+        let error = |text: &str| Guess::<u32>::parse(text, 1..=10).unwrap_err().to_string();
+        assert_eq!(
+            error("99999999999"),
+            "99999999999 is above the range, it has to be from 1 to 10"
+        );
+        assert_eq!(
+            error("-3"),
+            "-3 is below the range, it has to be from 1 to 10"
+        );
+        assert_eq!(
+            Guess::<i8>::parse("-300", -5..=5).unwrap_err().to_string(),
+            "-300 is below the range, it has to be from -5 to 5"
+        );
+        assert_eq!(error("-"), "'-' is not a whole number");
+        assert_eq!(Guess::new(7, 5..=10).map(Guess::value), Ok(7));
+    }
+}
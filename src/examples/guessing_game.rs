@@ -1,38 +0,0 @@
-use std::{cmp::Ordering, io};
-
-use rand::Rng;
-
-#[allow(unused)]
-pub fn guessing_game() {
-    println!("Guess the number!");
-
-    let secret_number = rand::thread_rng().gen_range(1..=10);
-
-    // println!("the secret number is {secret_number}");
-
-    loop {
-        println!("Please input your guess.");
-
-        let mut guess = String::new();
-
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
-
-        println!("You guessed: {guess}");
-
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
-            }
-        }
-    }
-}
@@ -0,0 +1,237 @@
+// the rules of the guessing game without any reading or printing,
+// so a front end can drive it from stdin, a test, a solver or a network connection
+// the secret comes from whatever random number generator is handed in,
+// a seeded one makes the game the same every time
+
+use std::{cmp::Ordering, ops::RangeInclusive};
+
+use rand::Rng;
+
+use super::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win { attempts: u32 },
+    // outside the range of the game, it does not cost an attempt
+    OutOfRange,
+    // the last attempt was wrong too, or the player gave up
+    OutOfAttempts { secret: u32 },
+}
+
+// how close a guess was, measured against the size of the whole range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Temperature {
+    Hot,
+    Warm,
+    Cool,
+    Cold,
+}
+
+impl Temperature {
+    pub fn name(self) -> &'static str {
+        match self {
+            Temperature::Hot => "hot",
+            Temperature::Warm => "warm",
+            Temperature::Cool => "cool",
+            Temperature::Cold => "cold",
+        }
+    }
+}
+
+// what a hint gives away: where the secret can still be, and whether it is even
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clue {
+    pub range: RangeInclusive<u32>,
+    pub even: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuessingGame {
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+    secret: u32,
+    attempts: u32,
+    // every guess that counted, with what it was told
+    history: Vec<(u32, Outcome)>,
+    // set once the game is won or lost, every guess after that gets the same outcome back
+    result: Option<Outcome>,
+}
+
+impl GuessingGame {
+    pub fn new<R: Rng + ?Sized>(config: &Config, rng: &mut R) -> GuessingGame {
+        let secret = rng.gen_range(config.range.clone());
+        GuessingGame::with_secret(config, secret)
+    }
+
+    // for when the secret is chosen some other way, by a test or by another player
+    pub fn with_secret(config: &Config, secret: u32) -> GuessingGame {
+        GuessingGame {
+            range: config.range.clone(),
+            max_attempts: config.max_attempts,
+            secret,
+            attempts: 0,
+            history: Vec::new(),
+            result: None,
+        }
+    }
+
+    pub fn guess(&mut self, n: u32) -> Outcome {
+        if let Some(result) = self.result {
+            return result;
+        }
+        if !self.range.contains(&n) {
+            return Outcome::OutOfRange;
+        }
+        self.attempts += 1;
+        let outcome = match n.cmp(&self.secret) {
+            Ordering::Equal => Outcome::Win {
+                attempts: self.attempts,
+            },
+            _ if self.attempts_left() == Some(0) => Outcome::OutOfAttempts {
+                secret: self.secret,
+            },
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+        };
+        if matches!(outcome, Outcome::Win { .. } | Outcome::OutOfAttempts { .. }) {
+            self.result = Some(outcome);
+        }
+        self.history.push((n, outcome));
+        outcome
+    }
+
+    pub fn temperature(&self, n: u32) -> Temperature {
+        let size = u64::from(self.range.end() - self.range.start()) + 1;
+        let distance = u64::from(n.abs_diff(self.secret));
+        // within 5% is hot, though one away is always hot, even in a small range
+        if distance <= (size / 20).max(1) {
+            Temperature::Hot
+        } else if distance <= size * 15 / 100 {
+            Temperature::Warm
+        } else if distance <= size * 30 / 100 {
+            Temperature::Cool
+        } else {
+            Temperature::Cold
+        }
+    }
+
+    // the range the guesses so far have narrowed it down to
+    pub fn known_range(&self) -> RangeInclusive<u32> {
+        let (mut low, mut high) = (*self.range.start(), *self.range.end());
+        for &(n, outcome) in &self.history {
+            match outcome {
+                Outcome::TooSmall => low = low.max(n + 1),
+                Outcome::TooBig => high = high.min(n - 1),
+                _ => {}
+            }
+        }
+        low..=high
+    }
+
+    // a hint costs an attempt, so there is none on the last one, or once the game is over
+    pub fn clue(&mut self) -> Option<Clue> {
+        if self.result.is_some() || self.attempts_left().is_some_and(|left| left <= 1) {
+            return None;
+        }
+        self.attempts += 1;
+        Some(Clue {
+            range: self.known_range(),
+            even: self.secret.is_multiple_of(2),
+        })
+    }
+
+    // giving up loses the game, the same way running out of attempts does
+    pub fn give_up(&mut self) -> u32 {
+        if self.result.is_none() {
+            self.result = Some(Outcome::OutOfAttempts {
+                secret: self.secret,
+            });
+        }
+        self.secret
+    }
+
+    pub fn history(&self) -> &[(u32, Outcome)] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    // for when the game has to end without a guess giving it away
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_game() {
+        let config = Config::default();
+        let secret = |seed| {
+            let mut game = GuessingGame::new(&config, &mut StdRng::seed_from_u64(seed));
+            (1..=10).find(|&n| matches!(game.guess(n), Outcome::Win { .. }))
+        };
+        assert_eq!(secret(7), secret(7));
+        assert!((1..100).map(secret).any(|s| s != secret(7)));
+    }
+
+    #[test]
+    fn outcomes_follow_the_rules() {
+        let config = Config {
+            range: 1..=100,
+            max_attempts: Some(3),
+            ..Config::default()
+        };
+        let mut game = GuessingGame::with_secret(&config, 42);
+        assert_eq!(game.guess(500), Outcome::OutOfRange);
+        assert_eq!(game.guess(10), Outcome::TooSmall);
+        assert_eq!(game.guess(50), Outcome::TooBig);
+        assert_eq!(game.attempts_left(), Some(1));
+        assert_eq!(game.guess(41), Outcome::OutOfAttempts { secret: 42 });
+        assert_eq!(game.guess(42), Outcome::OutOfAttempts { secret: 42 });
+
+        let mut game = GuessingGame::with_secret(&config, 42);
+        assert_eq!(game.guess(42), Outcome::Win { attempts: 1 });
+        assert_eq!(game.guess(7), Outcome::Win { attempts: 1 });
+    }
+
+    #[test]
+    fn hints_narrow_it_down_and_cost_an_attempt() {
+        let config = Config {
+            range: 1..=100,
+            max_attempts: Some(4),
+            ..Config::default()
+        };
+        let mut game = GuessingGame::with_secret(&config, 42);
+        assert_eq!(game.temperature(45), Temperature::Hot);
+        assert_eq!(game.temperature(55), Temperature::Warm);
+        assert_eq!(game.temperature(100), Temperature::Cold);
+        game.guess(20);
+        game.guess(60);
+        assert_eq!(
+            game.clue(),
+            Some(Clue {
+                range: 21..=59,
+                even: true
+            })
+        );
+        assert_eq!(game.attempts(), 3);
+        assert_eq!(game.clue(), None);
+        assert_eq!(game.history().len(), 2);
+        assert_eq!(game.give_up(), 42);
+        assert_eq!(game.guess(42), Outcome::OutOfAttempts { secret: 42 });
+    }
+}
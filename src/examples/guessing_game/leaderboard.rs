@@ -0,0 +1,421 @@
+// every finished game, kept per player in a json file so the scores outlive the program
+// the players are kept in a HashMap keyed by name, the same way collections.rs keeps team scores
+// several games can finish at the same time, so the file is only changed while holding a lock file,
+// and is always written whole to a temporary file that is renamed over the old one
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use super::Difficulty;
+use crate::{
+    examples::progress::{data_path, now},
+    json::Json,
+};
+
+pub const LEADERBOARD_FILE: &str = "leaderboard.json";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    pub range: RangeInclusive<u32>,
+    pub attempts: u32,
+    pub won: bool,
+    pub score: u32,
+    pub seconds: f64,
+    // seconds since the unix epoch
+    pub finished: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Leaderboard {
+    pub players: HashMap<String, Vec<GameResult>>,
+}
+
+pub fn leaderboard_path() -> Option<PathBuf> {
+    data_path("RUST_BOOK_LEADERBOARD", LEADERBOARD_FILE)
+}
+
+// the preset the range belongs to, or the range itself for a custom one
+pub fn category(range: &RangeInclusive<u32>) -> String {
+    [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+        .into_iter()
+        .find(|difficulty| difficulty.range() == *range)
+        .map(|difficulty| difficulty.name().to_string())
+        .unwrap_or_else(|| format!("custom {}..={}", range.start(), range.end()))
+}
+
+impl GameResult {
+    fn from_json(json: &Json) -> Option<GameResult> {
+        let number = |key| json.get(key).and_then(Json::as_f64);
+        Some(GameResult {
+            range: number("start")? as u32..=number("end")? as u32,
+            attempts: number("attempts")? as u32,
+            won: json.get("won") == Some(&Json::Bool(true)),
+            score: number("score")? as u32,
+            seconds: number("seconds")?,
+            finished: number("finished")? as u64,
+        })
+    }
+
+    fn to_json(&self) -> Json {
+        let number = |n: f64| Json::Number(n);
+        Json::Object(vec![
+            ("start".to_string(), number(f64::from(*self.range.start()))),
+            ("end".to_string(), number(f64::from(*self.range.end()))),
+            ("attempts".to_string(), number(f64::from(self.attempts))),
+            ("won".to_string(), Json::Bool(self.won)),
+            ("score".to_string(), number(f64::from(self.score))),
+            // to the millisecond is plenty
+            (
+                "seconds".to_string(),
+                number((self.seconds * 1000.0).round() / 1000.0),
+            ),
+            ("finished".to_string(), number(self.finished as f64)),
+        ])
+    }
+}
+
+// held while the file is read and written, the lock file is removed again when it is dropped
+// the lock file holds who owns it, so a lock that was taken over is not removed by its old owner
+// progress.rs locks the progress file with it too
+pub struct Lock {
+    path: PathBuf,
+    owner: String,
+}
+
+// a lock older than this was left behind by a run that died, so it is taken over
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+// the process id tells processes apart, the counter tells apart the locks of one process
+static LOCKS_TAKEN: AtomicUsize = AtomicUsize::new(0);
+
+// the owner written in the lock file, if it is older than STALE_LOCK
+fn stale_owner(path: &Path) -> Option<String> {
+    let age = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+    (age > STALE_LOCK).then(|| fs::read_to_string(path).unwrap_or_default())
+}
+
+impl Lock {
+    pub fn acquire(file: &Path) -> io::Result<Lock> {
+        let path = file.with_extension("json.lock");
+        let owner = format!(
+            "{} {}",
+            process::id(),
+            LOCKS_TAKEN.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut waited = Duration::ZERO;
+        loop {
+            // create_new fails if the file is there already, which makes it safe between processes
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut lock) => {
+                    write!(lock, "{owner}")?;
+                    return Ok(Lock { path, owner });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+            if let Some(stale) = stale_owner(&path) {
+                if Lock::break_stale(&path, &stale, &owner)? {
+                    continue;
+                }
+            }
+            if waited > Duration::from_secs(10) {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    format!(
+                        "{} is locked by another run, if nothing else is running remove {} \
+                         and {}.break",
+                        file.display(),
+                        path.display(),
+                        path.display()
+                    ),
+                ));
+            }
+            let pause = Duration::from_millis(5);
+            thread::sleep(pause);
+            waited += pause;
+        }
+    }
+
+    // two processes can both find the same stale lock, and the second must not remove the lock
+    // the first one took after it, so only the process that creates the breaker file may remove
+    // a stale lock, and only when it still has the owner it had when it was found to be stale
+    // the breaker file holds its owner too, a run that died while breaking leaves it behind,
+    // and once it is stale it is removed the same way, so the lock can be broken again
+    // true when the lock was removed
+    fn break_stale(path: &Path, stale: &str, owner: &str) -> io::Result<bool> {
+        let breaker = path.with_extension("lock.break");
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&breaker)
+        {
+            Ok(mut file) => write!(file, "{owner}")?,
+            // somebody else is breaking it, the lock is tried again after that
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                if let Some(dead) = stale_owner(&breaker) {
+                    if fs::read_to_string(&breaker).is_ok_and(|now| now == dead) {
+                        fs::remove_file(&breaker).ok();
+                    }
+                }
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        }
+        let broken = stale_owner(path).as_deref() == Some(stale) && fs::remove_file(path).is_ok();
+        fs::remove_file(&breaker)?;
+        Ok(broken)
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        if fs::read_to_string(&self.path).is_ok_and(|owner| owner == self.owner) {
+            fs::remove_file(&self.path).ok();
+        }
+    }
+}
+
+impl Leaderboard {
+    // a file that does not exist yet is an empty leaderboard
+    pub fn load(path: &Path) -> Result<Leaderboard, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Leaderboard::default()),
+            Err(e) => return Err(format!("can not read {}: {e}", path.display())),
+        };
+        let json = Json::parse(&text).map_err(|e| format!("{} is broken: {e}", path.display()))?;
+        let mut leaderboard = Leaderboard::default();
+        for (player, results) in json.get("players").and_then(Json::as_object).unwrap_or(&[]) {
+            let results = match results {
+                Json::Array(results) => results.iter().filter_map(GameResult::from_json).collect(),
+                _ => Vec::new(),
+            };
+            leaderboard.players.insert(player.clone(), results);
+        }
+        Ok(leaderboard)
+    }
+
+    fn to_json(&self) -> Json {
+        // sorted by name, so the file does not reshuffle every time it is written
+        let mut players: Vec<_> = self.players.iter().collect();
+        players.sort_by(|a, b| a.0.cmp(b.0));
+        let players = players
+            .into_iter()
+            .map(|(player, results)| {
+                let results = results.iter().map(GameResult::to_json).collect();
+                (player.clone(), Json::Array(results))
+            })
+            .collect();
+        Json::Object(vec![
+            ("version".to_string(), Json::Number(1.0)),
+            ("players".to_string(), Json::Object(players)),
+        ])
+    }
+
+    // adds the result to what is in the file right now, not to what was there when the game began
+    pub fn record(path: &Path, player: &str, result: GameResult) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("can not create {}: {e}", dir.display()))?;
+        }
+        let _lock =
+            Lock::acquire(path).map_err(|e| format!("can not lock the leaderboard: {e}"))?;
+        let mut leaderboard = Leaderboard::load(path)?;
+        leaderboard
+            .players
+            .entry(player.to_string())
+            .or_default()
+            .push(result);
+
+        // every process writes to a file of its own, in case a stale lock was taken over
+        let tmp = path.with_extension(format!("json.{}.tmp", process::id()));
+        fs::write(&tmp, leaderboard.to_json().pretty() + "\n")
+            .and_then(|()| fs::rename(&tmp, path))
+            .map_err(|e| format!("can not write {}: {e}", path.display()))
+    }
+
+    // the best win of every player for each kind of game, best first
+    // a higher score wins, then fewer attempts, then the quicker game
+    pub fn top(&self, limit: usize) -> Vec<(String, Vec<(&str, &GameResult)>)> {
+        let mut categories: HashMap<String, Vec<(&str, &GameResult)>> = HashMap::new();
+        for (player, results) in &self.players {
+            let mut best: HashMap<String, &GameResult> = HashMap::new();
+            for result in results.iter().filter(|result| result.won) {
+                let entry = best.entry(category(&result.range)).or_insert(result);
+                if rank(result, entry).is_lt() {
+                    *entry = result;
+                }
+            }
+            for (category, result) in best {
+                categories
+                    .entry(category)
+                    .or_default()
+                    .push((player.as_str(), result));
+            }
+        }
+        let mut categories: Vec<_> = categories.into_iter().collect();
+        for (_, results) in &mut categories {
+            results.sort_by(|a, b| rank(a.1, b.1).then(a.0.cmp(b.0)));
+            results.truncate(limit);
+        }
+        // the presets in order of difficulty, then the custom ranges
+        categories.sort_by_key(|(category, _)| {
+            let order = ["easy", "normal", "hard"]
+                .iter()
+                .position(|name| name == category);
+            (order.unwrap_or(3), category.clone())
+        });
+        categories
+    }
+}
+
+fn rank(a: &GameResult, b: &GameResult) -> std::cmp::Ordering {
+    b.score
+        .cmp(&a.score)
+        .then(a.attempts.cmp(&b.attempts))
+        .then(a.seconds.total_cmp(&b.seconds))
+}
+
+pub fn show(leaderboard: &Leaderboard, only: Option<&str>, out: &mut dyn Write) -> io::Result<()> {
+    let top = leaderboard.top(10);
+    let mut shown = false;
+    for (category, results) in &top {
+        if only.is_some_and(|only| only != category) {
+            continue;
+        }
+        shown = true;
+        writeln!(out, "{category}")?;
+        for (place, (player, result)) in results.iter().enumerate() {
+            writeln!(
+                out,
+                "  {:>2}. {:<16} score {:>3}  {:>3} attempts  {:>7.1}s  {}",
+                place + 1,
+                player,
+                result.score,
+                result.attempts,
+                result.seconds,
+                days_ago(result.finished)
+            )?;
+        }
+    }
+    if !shown {
+        writeln!(out, "no games won yet")?;
+    }
+    Ok(())
+}
+
+fn days_ago(then: u64) -> String {
+    match now().saturating_sub(then) / 86_400 {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{days} days ago"),
+    }
+}
+
+#[cfg(test)]
+mod leaderboard_tests {
+    use super::*;
+
+    fn result(range: RangeInclusive<u32>, attempts: u32, score: u32) -> GameResult {
+        GameResult {
+            range,
+            attempts,
+            won: score > 0,
+            score,
+            seconds: 1.5,
+            finished: 0,
+        }
+    }
+
+    #[test]
+    fn concurrent_games_all_get_recorded() {
+        let dir = std::env::temp_dir().join(format!("leaderboard-test-{}", process::id()));
+        let path = dir.join(LEADERBOARD_FILE);
+        let games: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    let player = if i % 2 == 0 { "ada" } else { "bob" };
+                    Leaderboard::record(&path, player, result(1..=10, 4 + i, 100 - i)).unwrap();
+                })
+            })
+            .collect();
+        for game in games {
+            game.join().unwrap();
+        }
+        let leaderboard = Leaderboard::load(&path).unwrap();
+        assert_eq!(leaderboard.players["ada"].len(), 4);
+        assert_eq!(leaderboard.players["bob"].len(), 4);
+        assert!(!path.with_extension("json.lock").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_stale_lock_is_taken_over() {
+        let dir = std::env::temp_dir().join(format!("leaderboard-stale-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(LEADERBOARD_FILE);
+        let lock_path = path.with_extension("json.lock");
+        let left_behind = fs::File::create(&lock_path).unwrap();
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        left_behind.set_modified(an_hour_ago).unwrap();
+
+        let lock = Lock::acquire(&path).unwrap();
+        assert_eq!(fs::read_to_string(&lock_path).unwrap(), lock.owner);
+        // a lock that is not stale yet is left alone
+        assert!(!Lock::break_stale(&lock_path, "", "test").unwrap());
+        assert!(lock_path.exists());
+        drop(lock);
+        assert!(!lock_path.exists());
+
+        // a breaker file left by a run that died while breaking a lock is cleared once it is stale
+        let breaker_path = path.with_extension("json.lock.break");
+        for left in [&lock_path, &breaker_path] {
+            let file = fs::File::create(left).unwrap();
+            file.set_modified(an_hour_ago).unwrap();
+        }
+        drop(Lock::acquire(&path).unwrap());
+        assert!(!lock_path.exists());
+        assert!(!breaker_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn top_keeps_the_best_win_of_each_player() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.players.insert(
+            "ada".to_string(),
+            vec![
+                result(1..=10, 6, 66),
+                result(1..=10, 3, 100),
+                result(1..=100, 9, 0),
+            ],
+        );
+        leaderboard.players.insert(
+            "bob".to_string(),
+            vec![result(1..=100, 8, 87), result(5..=6, 1, 100)],
+        );
+        let top = leaderboard.top(10);
+        let categories: Vec<&str> = top.iter().map(|(category, _)| category.as_str()).collect();
+        assert_eq!(categories, ["easy", "normal", "custom 5..=6"]);
+        assert_eq!(top[0].1.len(), 1);
+        assert_eq!(top[0].1[0].1.attempts, 3);
+
+        let mut out = Vec::new();
+        show(&leaderboard, Some("normal"), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("normal\n   1. bob              score  87"));
+    }
+}
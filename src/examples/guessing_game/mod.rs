@@ -0,0 +1,672 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    io::{self, BufRead, Write},
+    ops::{ControlFlow, RangeInclusive},
+    time::Instant,
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{
+    guess::{Guess, GuessError},
+    prompt::{Prompt, PromptError},
+    Example,
+};
+use game::Outcome;
+use leaderboard::GameResult;
+use recording::Transcript;
+use solver::{Hint, Solver};
+
+pub mod game;
+pub mod leaderboard;
+pub mod recording;
+pub mod server;
+pub mod solver;
+pub mod words;
+
+pub struct GuessingGame;
+
+impl Example for GuessingGame {
+    fn name(&self) -> &'static str {
+        "guessing_game"
+    }
+
+    fn chapter(&self) -> u32 {
+        2
+    }
+
+    fn description(&self) -> &'static str {
+        "guess a secret number read from stdin"
+    }
+
+    fn run(&self, out: &mut dyn Write, input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        guessing_game(out, input, &Config::default())?;
+        Ok(())
+    }
+}
+
+// the presets for the range the secret number is picked from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(name: &str) -> Result<Difficulty, String> {
+        match name {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(format!(
+                "unknown difficulty '{other}', expected easy, normal or hard"
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+}
+
+// who picks the number and who guesses it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    // the computer picks, the player guesses, as in the book
+    Play,
+    // the computer picks and guesses, explaining every guess
+    Solver,
+    // the player picks and answers higher or lower, the computer guesses
+    Reverse,
+    // a secret word instead of a number, see words.rs
+    Wordle,
+    Hangman,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub range: RangeInclusive<u32>,
+    // None lets the player guess for as long as they like
+    pub max_attempts: Option<u32>,
+    // the same seed picks the same secret, None picks a new one every game
+    pub seed: Option<u64>,
+    pub mode: Mode,
+    // say how hot or cold every wrong guess was
+    pub hints: bool,
+}
+
+// the game from the book picks from 1 to 10 and never runs out of guesses
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            range: Difficulty::Easy.range(),
+            max_attempts: None,
+            seed: None,
+            mode: Mode::Play,
+            hints: false,
+        }
+    }
+}
+
+// "1..=50", "1..50" (the end left out, like a rust range) or "1-50"
+fn parse_range(text: &str) -> Result<RangeInclusive<u32>, String> {
+    let invalid = || format!("'{text}' is not a range, try something like 1..=50");
+    let (start, end, inclusive) = if let Some((start, end)) = text.split_once("..=") {
+        (start, end, true)
+    } else if let Some((start, end)) = text.split_once("..") {
+        (start, end, false)
+    } else if let Some((start, end)) = text.split_once('-') {
+        (start, end, true)
+    } else {
+        return Err(invalid());
+    };
+    let start: u32 = start.trim().parse().map_err(|_| invalid())?;
+    let end: u32 = end.trim().parse().map_err(|_| invalid())?;
+    let end = if inclusive {
+        end
+    } else {
+        end.checked_sub(1).ok_or_else(invalid)?
+    };
+    if start > end {
+        return Err(format!("the range {text} is empty"));
+    }
+    Ok(start..=end)
+}
+
+impl Config {
+    // --difficulty easy|normal|hard, --range <start>..=<end>, --attempts <n> and --seed <n>
+    // a value can follow its flag, or be attached with =
+    // --solver, --reverse, --wordle, --hangman and --hints take no value
+    pub fn build(args: &[&str]) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+                _ => (*arg, None),
+            };
+            let mut value = || {
+                inline
+                    .or_else(|| args.next().copied())
+                    .ok_or_else(|| format!("{flag} needs a value"))
+            };
+            match flag {
+                "--difficulty" => config.range = Difficulty::parse(value()?)?.range(),
+                "--range" => config.range = parse_range(value()?)?,
+                "--attempts" => {
+                    let value = value()?;
+                    match value.parse() {
+                        Ok(0) | Err(_) => {
+                            return Err(format!("--attempts takes a number above 0, not '{value}'"))
+                        }
+                        Ok(n) => config.max_attempts = Some(n),
+                    }
+                }
+                "--seed" => {
+                    let value = value()?;
+                    let seed = value
+                        .parse()
+                        .map_err(|_| format!("--seed takes a whole number, not '{value}'"))?;
+                    config.seed = Some(seed);
+                }
+                "--solver" if inline.is_none() => config.mode = Mode::Solver,
+                "--reverse" if inline.is_none() => config.mode = Mode::Reverse,
+                "--wordle" if inline.is_none() => config.mode = Mode::Wordle,
+                "--hangman" if inline.is_none() => config.mode = Mode::Hangman,
+                "--hints" if inline.is_none() => config.hints = true,
+                other => return Err(format!("unexpected argument '{other}'")),
+            }
+        }
+        Ok(config)
+    }
+
+    // how many guesses halving the range every time needs at worst,
+    // one for every binary digit of the number of numbers in it
+    pub fn optimal_attempts(&self) -> u32 {
+        let size = u64::from(self.range.end() - self.range.start()) + 1;
+        u64::BITS - size.leading_zeros()
+    }
+
+    // 100 for finding it in the optimal number of guesses or fewer,
+    // less the more guesses it took beyond that
+    pub fn score(&self, attempts: u32) -> u32 {
+        (self.optimal_attempts() * 100 / attempts.max(1)).min(100)
+    }
+}
+
+// a u64 too, the whole u32 range holds one number more than a u32 can count
+fn plural(n: impl Into<u64>, one: &str, many: &str) -> String {
+    let n = n.into();
+    format!("{n} {}", if n == 1 { one } else { many })
+}
+
+impl Config {
+    // seeded from the config, or from the thread's own generator when there is no seed
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(rand::thread_rng()).expect("the thread rng does not fail"),
+        }
+    }
+}
+
+// the answer to the prompt, or None once the input has run out
+fn next_answer<T: Clone>(
+    prompt: &Prompt<T>,
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+) -> io::Result<Option<T>> {
+    match prompt.ask(out, input) {
+        Ok(answer) => Ok(Some(answer)),
+        Err(PromptError::Closed { .. }) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// the loop every guessing game is played on, the number game and the word games in words.rs:
+// ask, let the game answer, and stop when a turn says so or the input runs out
+// hands back what the last turn ended with, or None when the input ran out
+fn game_loop<T: Clone, R>(
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+    prompt: &Prompt<T>,
+    mut turn: impl FnMut(&mut dyn Write, T) -> io::Result<ControlFlow<Option<R>>>,
+) -> io::Result<Option<R>> {
+    loop {
+        let Some(answer) = next_answer(prompt, out, input)? else {
+            writeln!(out, "No more guesses, the game ends here.")?;
+            return Ok(None);
+        };
+        if let ControlFlow::Break(end) = turn(out, answer)? {
+            return Ok(end);
+        }
+    }
+}
+
+const COMMANDS: &str = "hint, history, giveup or quit";
+
+// what the player can type instead of a guess
+#[derive(Debug, Clone, Copy)]
+enum Move {
+    Guess(u32),
+    Hint,
+    History,
+    GiveUp,
+    Quit,
+}
+
+// a line the game turns down, with the reason told to the player and the outcome for the log
+struct Rejected {
+    reason: String,
+    outcome: &'static str,
+}
+
+fn parse_move(text: &str, range: &RangeInclusive<u32>) -> Result<Move, Rejected> {
+    match text {
+        "hint" => Ok(Move::Hint),
+        "history" => Ok(Move::History),
+        "giveup" => Ok(Move::GiveUp),
+        "quit" => Ok(Move::Quit),
+        text => match Guess::parse(text, range.clone()) {
+            Ok(guess) => Ok(Move::Guess(guess.value())),
+            // outside the range it does not cost an attempt
+            Err(e @ GuessError::OutOfRange { .. }) => Err(Rejected {
+                reason: e.to_string(),
+                outcome: "out of range",
+            }),
+            Err(e) => Err(Rejected {
+                reason: format!("{e}, guess one or type {COMMANDS}"),
+                outcome: "not a number",
+            }),
+        },
+    }
+}
+
+fn show_history(out: &mut dyn Write, game: &game::GuessingGame) -> io::Result<()> {
+    if game.history().is_empty() {
+        return writeln!(out, "No guesses yet.");
+    }
+    for (number, (guess, outcome)) in game.history().iter().enumerate() {
+        let said = match outcome {
+            Outcome::TooSmall => "too small",
+            Outcome::TooBig => "too big",
+            _ => "the last one",
+        };
+        writeln!(out, "{}. {guess}, {said}", number + 1)?;
+    }
+    Ok(())
+}
+
+// the game from the book, reading guesses from input, the rules are in game.rs
+// besides a number the player can type one of the COMMANDS
+// hands back how the game went for the leaderboard, or None when the player quit
+pub fn guessing_game(
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+    config: &Config,
+) -> io::Result<Option<GameResult>> {
+    Ok(recorded_game(out, input, config)?.0)
+}
+
+// the same game, also handing back what it made of every line that was typed, for recording.rs
+pub fn recorded_game(
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+    config: &Config,
+) -> io::Result<(Option<GameResult>, Transcript)> {
+    writeln!(out, "Guess the number!")?;
+    let (low, high) = (*config.range.start(), *config.range.end());
+    match config.max_attempts {
+        Some(max) => writeln!(
+            out,
+            "I am thinking of a number from {low} to {high}, you have {}.",
+            plural(max, "attempt", "attempts")
+        )?,
+        None => writeln!(out, "I am thinking of a number from {low} to {high}.")?,
+    }
+
+    let mut game = game::GuessingGame::new(config, &mut config.rng());
+    let started = Instant::now();
+    let transcript = RefCell::new(Transcript::default());
+
+    let guesses = Prompt::parse_with("Please input your guess.", |text| {
+        let parsed = parse_move(text, &config.range);
+        // a line the prompt turns down never gets to the loop below, so it is noted here
+        let outcome = parsed
+            .as_ref()
+            .err()
+            .map_or("", |rejected| rejected.outcome);
+        transcript.borrow_mut().typed(started, text, outcome);
+        parsed.map_err(|rejected| rejected.reason)
+    });
+    let answered = |outcome: &str| transcript.borrow_mut().answered(outcome);
+
+    // None when the player quit or the input ran out
+    let won = game_loop(out, input, &guesses, |out, next| {
+        let guess = match next {
+            Move::Quit => {
+                answered("quit");
+                writeln!(out, "Bye!")?;
+                return Ok(ControlFlow::Break(None));
+            }
+            Move::GiveUp => {
+                answered("gave up");
+                let secret = game.give_up();
+                writeln!(out, "The secret number was {secret}.")?;
+                return Ok(ControlFlow::Break(Some(false)));
+            }
+            Move::History => {
+                answered("history");
+                show_history(out, &game)?;
+                return Ok(ControlFlow::Continue(()));
+            }
+            Move::Hint => {
+                match game.clue() {
+                    Some(clue) => {
+                        answered("hint");
+                        writeln!(
+                            out,
+                            "It is from {} to {}, and it is {}. That cost you a guess.",
+                            clue.range.start(),
+                            clue.range.end(),
+                            if clue.even { "even" } else { "odd" }
+                        )?
+                    }
+                    None => {
+                        answered("no hint");
+                        writeln!(out, "No hints on your last attempt.")?
+                    }
+                }
+                return Ok(ControlFlow::Continue(()));
+            }
+            Move::Guess(guess) => guess,
+        };
+
+        writeln!(out, "You guessed: {guess}")?;
+
+        let temperature = if config.hints {
+            format!(" You are {}.", game.temperature(guess).name())
+        } else {
+            String::new()
+        };
+        match game.guess(guess) {
+            Outcome::TooSmall => {
+                answered("too small");
+                writeln!(out, "Too small!{temperature}")?
+            }
+            Outcome::TooBig => {
+                answered("too big");
+                writeln!(out, "Too big!{temperature}")?
+            }
+            Outcome::OutOfRange => {
+                answered("out of range");
+                writeln!(out, "The number is from {low} to {high}.")?
+            }
+            Outcome::OutOfAttempts { secret } => {
+                answered("out of attempts");
+                writeln!(out, "Out of attempts! The secret number was {secret}.")?;
+                return Ok(ControlFlow::Break(Some(false)));
+            }
+            Outcome::Win { attempts } => {
+                answered("correct");
+                writeln!(out, "You win!")?;
+                writeln!(
+                    out,
+                    "You took {}, halving the range every time needs at most {}. Score: {}",
+                    plural(attempts, "guess", "guesses"),
+                    config.optimal_attempts(),
+                    config.score(attempts)
+                )?;
+                return Ok(ControlFlow::Break(Some(true)));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    })?;
+
+    drop(guesses);
+    let attempts = game.attempts();
+    let transcript = transcript.into_inner().ended(won, attempts);
+    let result = won.map(|won| GameResult {
+        range: config.range.clone(),
+        attempts,
+        won,
+        score: if won { config.score(attempts) } else { 0 },
+        seconds: started.elapsed().as_secs_f64(),
+        finished: super::progress::now(),
+    });
+    Ok((result, transcript))
+}
+
+// the computer plays against a secret of its own, showing binary search at work
+pub fn solve(out: &mut dyn Write, config: &Config) -> io::Result<()> {
+    let mut game = game::GuessingGame::new(config, &mut config.rng());
+    let mut solver = Solver::new(config.range.clone());
+    writeln!(
+        out,
+        "The secret number is from {} to {}, halving the range every time finds it in at most {}.",
+        config.range.start(),
+        config.range.end(),
+        plural(config.optimal_attempts(), "guess", "guesses")
+    )?;
+    loop {
+        let remaining = solver.remaining();
+        let guess = solver.guess();
+        writeln!(
+            out,
+            "I guess {guess}, the middle of {} to {}, which rules out about half of the {} left.",
+            remaining.start(),
+            remaining.end(),
+            plural(
+                u64::from(remaining.end() - remaining.start()) + 1,
+                "number",
+                "numbers"
+            )
+        )?;
+        let hint = match game.guess(guess) {
+            Outcome::TooSmall => Hint::Higher,
+            Outcome::TooBig => Hint::Lower,
+            Outcome::Win { attempts } => {
+                writeln!(
+                    out,
+                    "You win! Found it in {}.",
+                    plural(attempts, "guess", "guesses")
+                )?;
+                return Ok(());
+            }
+            Outcome::OutOfAttempts { secret } => {
+                writeln!(out, "Out of attempts! The secret number was {secret}.")?;
+                return Ok(());
+            }
+            // the solver never leaves the range
+            Outcome::OutOfRange => unreachable!("{guess} is outside {:?}", config.range),
+        };
+        solver
+            .answer(guess, hint)
+            .expect("the game does not contradict itself");
+        let remaining = solver.remaining();
+        writeln!(
+            out,
+            "{}, so it is from {} to {}.",
+            if hint == Hint::Higher {
+                "Too small"
+            } else {
+                "Too big"
+            },
+            remaining.start(),
+            remaining.end()
+        )?;
+    }
+}
+
+// the player thinks of a number and the computer guesses it
+pub fn reversed(out: &mut dyn Write, input: &mut dyn BufRead, config: &Config) -> io::Result<()> {
+    let (low, high) = (*config.range.start(), *config.range.end());
+    writeln!(
+        out,
+        "Think of a number from {low} to {high}, and I will guess it."
+    )?;
+    let mut solver = Solver::new(config.range.clone());
+    loop {
+        if config.max_attempts == Some(solver.attempts()) {
+            writeln!(out, "I am out of attempts, you win!")?;
+            return Ok(());
+        }
+        let guess = solver.guess();
+        let question = format!("Is it {guess}? (higher, lower or correct)");
+        let answers = Prompt::parse_with(&question, |text| {
+            Hint::parse(text).ok_or_else(|| "Please answer higher, lower or correct".to_string())
+        });
+        let Some(hint) = next_answer(&answers, out, input)? else {
+            writeln!(out, "No more answers, I give up.")?;
+            return Ok(());
+        };
+        if let Err(contradiction) = solver.answer(guess, hint) {
+            writeln!(out, "That can not be right, {contradiction}.")?;
+            continue;
+        }
+        if hint == Hint::Correct {
+            writeln!(
+                out,
+                "Got it in {}!",
+                plural(solver.attempts(), "guess", "guesses")
+            )?;
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod guessing_game_tests {
+    use super::*;
+
+    #[test]
+    fn flags_pick_the_range_and_attempts() {
+        let config = Config::build(&["--difficulty", "hard", "--attempts=5"]).unwrap();
+        assert_eq!(config.range, 1..=1000);
+        assert_eq!(config.max_attempts, Some(5));
+        assert_eq!(Config::build(&["--range", "5..20"]).unwrap().range, 5..=19);
+        assert_eq!(Config::build(&["--range=5-20"]).unwrap().range, 5..=20);
+        assert!(Config::build(&["--range", "20..=5"]).is_err());
+        assert!(Config::build(&["--attempts", "0"]).is_err());
+    }
+
+    #[test]
+    fn score_compares_with_binary_search() {
+        let config = Config::default();
+        assert_eq!(config.optimal_attempts(), 4);
+        assert_eq!(config.score(3), 100);
+        assert_eq!(config.score(8), 50);
+        let hard = Config {
+            range: Difficulty::Hard.range(),
+            ..Config::default()
+        };
+        assert_eq!(hard.optimal_attempts(), 10);
+    }
+
+    #[test]
+    fn guesses_outside_the_range_are_free() {
+        let config = Config {
+            range: 1..=1,
+            max_attempts: Some(1),
+            ..Config::default()
+        };
+        let mut out = Vec::new();
+        guessing_game(&mut out, &mut "7\n1\n".as_bytes(), &config).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("7 is above the range, it has to be from 1 to 1."));
+        assert!(out.contains("You win!"));
+    }
+
+    #[test]
+    fn a_seeded_game_reveals_the_secret_on_a_loss() {
+        let config =
+            Config::build(&["--range", "1..=2", "--attempts", "1", "--seed", "3"]).unwrap();
+        let mut game = game::GuessingGame::new(&config, &mut config.rng());
+        let wrong = match game.guess(1) {
+            Outcome::Win { .. } => 2,
+            _ => 1,
+        };
+        let secret = 3 - wrong;
+
+        let mut out = Vec::new();
+        guessing_game(&mut out, &mut format!("{wrong}\n").as_bytes(), &config).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with(&format!(
+            "Out of attempts! The secret number was {secret}.\n"
+        )));
+    }
+
+    #[test]
+    fn the_solver_explains_its_guesses() {
+        let config = Config::build(&["--difficulty", "normal", "--seed", "1", "--solver"]).unwrap();
+        assert_eq!(config.mode, Mode::Solver);
+        let mut out = Vec::new();
+        solve(&mut out, &config).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("I guess 50, the middle of 1 to 100"));
+        assert!(out.contains("You win! Found it in"));
+
+        // the whole u32 range has one number more than a u32 can count
+        let config = Config::build(&["--range", "0..=4294967295", "--seed", "1"]).unwrap();
+        let mut out = Vec::new();
+        solve(&mut out, &config).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("at most 33 guesses"));
+        assert!(out.contains("rules out about half of the 4294967296 numbers left"));
+    }
+
+    #[test]
+    fn reversed_catches_contradicting_answers() {
+        let config = Config::build(&["--range", "1..=4", "--reverse"]).unwrap();
+        let mut out = Vec::new();
+        let mut answers = "maybe\nhigher\nlower\ncorrect\n".as_bytes();
+        reversed(&mut out, &mut answers, &config).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Is it 2? (higher, lower or correct)\nPlease answer"));
+        assert!(out.contains("Is it 3?"));
+        assert!(out.contains("That can not be right, you said higher than 2 earlier."));
+        assert!(out.ends_with("Got it in 2 guesses!\n"));
+
+        let mut out = Vec::new();
+        reversed(&mut out, &mut "lower\n".as_bytes(), &config).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("No more answers, I give up.\n"));
+    }
+
+    #[test]
+    fn commands_and_the_end_of_input() {
+        let config = Config::build(&["--range", "1..=100", "--seed", "5", "--hints"]).unwrap();
+        let mut out = Vec::new();
+        let mut input = "history\nfifty\n50\nhint\nhistory\n".as_bytes();
+        let result = guessing_game(&mut out, &mut input, &config).unwrap();
+        assert_eq!(result, None);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("No guesses yet."));
+        assert!(out.contains("'fifty' is not a whole number, guess one or type hint, history"));
+        assert!(out.contains("You guessed: 50\nToo") && out.contains("You are"));
+        assert!(out.contains("That cost you a guess."));
+        assert!(out.contains("\n1. 50, too"));
+        assert!(out.ends_with("No more guesses, the game ends here.\n"));
+
+        let mut out = Vec::new();
+        let result = guessing_game(&mut out, &mut "giveup\n".as_bytes(), &config).unwrap();
+        let result = result.unwrap();
+        assert!(!result.won);
+        assert_eq!(result.score, 0);
+    }
+}
@@ -0,0 +1,365 @@
+// a log of played games, to look back at how people play
+// the game loop writes down every line with the time it arrived and what it made of it,
+// and together with the seed the lines are enough to play the whole game again
+// a log file holds one game per line, as json, so games can be added to it without reading it
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, BufRead, Write},
+    ops::RangeInclusive,
+    path::Path,
+    time::Instant,
+};
+
+use super::{plural, recorded_game, Config};
+use crate::json::Json;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    // seconds since the game started
+    pub at: f64,
+    pub input: String,
+    // what the game made of it, "too small", "hint", "not a number"...
+    pub outcome: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub seed: u64,
+    pub range: RangeInclusive<u32>,
+    pub max_attempts: Option<u32>,
+    // seconds since the unix epoch
+    pub started: u64,
+    pub steps: Vec<Step>,
+    // "won", "lost" or "quit"
+    pub result: String,
+    pub attempts: u32,
+}
+
+// what the game made of every line typed into it, written down by the game loop as it goes
+#[derive(Debug, Default)]
+pub struct Transcript {
+    pub steps: Vec<Step>,
+    // "won", "lost" or "quit"
+    pub result: String,
+    pub attempts: u32,
+}
+
+impl Transcript {
+    // a line as it was read, the outcome is filled in by answered when the game gets to it
+    pub fn typed(&mut self, started: Instant, input: &str, outcome: &str) {
+        // to the millisecond is plenty
+        let at = (started.elapsed().as_secs_f64() * 1000.0).round() / 1000.0;
+        self.steps.push(Step {
+            at,
+            input: input.to_string(),
+            outcome: outcome.to_string(),
+        });
+    }
+
+    pub fn answered(&mut self, outcome: &str) {
+        if let Some(step) = self.steps.last_mut() {
+            step.outcome = outcome.to_string();
+        }
+    }
+
+    // None when the player quit or the input ran out
+    pub fn ended(self, won: Option<bool>, attempts: u32) -> Transcript {
+        let result = match won {
+            Some(true) => "won",
+            Some(false) => "lost",
+            None => "quit",
+        };
+        Transcript {
+            result: result.to_string(),
+            attempts,
+            ..self
+        }
+    }
+}
+
+impl Session {
+    // the config needs a seed, so the game can be played again from the log
+    pub fn new(config: &Config, started: u64, transcript: Transcript) -> Result<Session, String> {
+        let seed = config
+            .seed
+            .ok_or("a game can only be recorded with a seed, give one with --seed")?;
+        Ok(Session {
+            seed,
+            range: config.range.clone(),
+            max_attempts: config.max_attempts,
+            started,
+            steps: transcript.steps,
+            result: transcript.result,
+            attempts: transcript.attempts,
+        })
+    }
+
+    pub fn config(&self) -> Config {
+        Config {
+            range: self.range.clone(),
+            max_attempts: self.max_attempts,
+            seed: Some(self.seed),
+            ..Config::default()
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let text = |s: &str| Json::String(s.to_string());
+        let number = |n: f64| Json::Number(n);
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                Json::Object(vec![
+                    ("at".to_string(), number(step.at)),
+                    ("input".to_string(), text(&step.input)),
+                    ("outcome".to_string(), text(&step.outcome)),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            // as text, a u64 does not fit in a json number
+            ("seed".to_string(), text(&self.seed.to_string())),
+            ("start".to_string(), number(f64::from(*self.range.start()))),
+            ("end".to_string(), number(f64::from(*self.range.end()))),
+            (
+                "max_attempts".to_string(),
+                self.max_attempts
+                    .map_or(Json::Null, |max| number(f64::from(max))),
+            ),
+            ("started".to_string(), number(self.started as f64)),
+            ("steps".to_string(), Json::Array(steps)),
+            ("result".to_string(), text(&self.result)),
+            ("attempts".to_string(), number(f64::from(self.attempts))),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Option<Session> {
+        let number = |key| json.get(key).and_then(Json::as_u64);
+        // a number too big for a u32 is a broken log, not one to wrap around to a small number
+        let small = |key| number(key).and_then(|n| u32::try_from(n).ok());
+        let text = |json: &Json, key| json.get(key).and_then(Json::as_str).map(str::to_string);
+        let steps = match json.get("steps")? {
+            Json::Array(steps) => steps
+                .iter()
+                .map(|step| {
+                    Some(Step {
+                        at: step.get("at").and_then(Json::as_f64)?,
+                        input: text(step, "input")?,
+                        outcome: text(step, "outcome")?,
+                    })
+                })
+                .collect::<Option<Vec<Step>>>()?,
+            _ => return None,
+        };
+        Some(Session {
+            seed: text(json, "seed")?.parse().ok()?,
+            range: small("start")?..=small("end")?,
+            max_attempts: match json.get("max_attempts")? {
+                Json::Null => None,
+                _ => Some(small("max_attempts")?),
+            },
+            started: number("started")?,
+            steps,
+            result: text(json, "result")?,
+            attempts: small("attempts")?,
+        })
+    }
+
+    // adds the game to the end of the log, which is created when it is not there yet
+    pub fn append(&self, path: &Path) -> io::Result<()> {
+        let mut log = OpenOptions::new().create(true).append(true).open(path)?;
+        // one write for the whole line, so games that end together do not get mixed up
+        log.write_all(format!("{}\n", self.to_json()).as_bytes())
+    }
+
+    // the same inputs played again from the seed should end up the same way,
+    // if they do not the log was changed or the game rules were
+    pub fn check(&self) -> Result<(), String> {
+        let inputs: String = self
+            .steps
+            .iter()
+            .map(|step| format!("{}\n", step.input))
+            .collect();
+        let (_, replayed) = recorded_game(&mut io::sink(), &mut inputs.as_bytes(), &self.config())
+            .map_err(|e| format!("could not play it again: {e}"))?;
+        // only when the lines came is left out, it can not be the same twice
+        let outcomes = |steps: &[Step]| -> Vec<(String, String)> {
+            steps
+                .iter()
+                .map(|step| (step.input.clone(), step.outcome.clone()))
+                .collect()
+        };
+        if outcomes(&replayed.steps) != outcomes(&self.steps) || replayed.result != self.result {
+            return Err("playing it again from the seed ends differently".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub fn load(path: &Path) -> Result<Vec<Session>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("can not read {}: {e}", path.display()))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let broken = |e: String| format!("{}:{}: {e}", path.display(), index + 1);
+            let json = Json::parse(line).map_err(broken)?;
+            Session::from_json(&json).ok_or_else(|| broken("not a recorded game".to_string()))
+        })
+        .collect()
+}
+
+// one step at a time, the next one comes when enter is pressed,
+// once the input is closed the rest is shown without waiting
+pub fn show_steps(
+    sessions: &[Session],
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+) -> io::Result<()> {
+    let mut waiting = true;
+    let mut next_step = |out: &mut dyn Write| -> io::Result<()> {
+        out.flush()?;
+        if waiting {
+            waiting = input.read_line(&mut String::new())? > 0;
+        }
+        Ok(())
+    };
+    writeln!(out, "press enter for the next step")?;
+    for (number, session) in sessions.iter().enumerate() {
+        writeln!(
+            out,
+            "game {} of {}: from {} to {}, seed {}",
+            number + 1,
+            sessions.len(),
+            session.range.start(),
+            session.range.end(),
+            session.seed
+        )?;
+        for step in &session.steps {
+            writeln!(
+                out,
+                "  {:>7.1}s  {:<10} {}",
+                step.at, step.input, step.outcome
+            )?;
+            next_step(out)?;
+        }
+        let checked = match session.check() {
+            Ok(()) => String::new(),
+            Err(e) => format!(" ({e})"),
+        };
+        writeln!(
+            out,
+            "  {} after {}{checked}",
+            session.result,
+            plural(session.attempts, "attempt", "attempts")
+        )?;
+    }
+    Ok(())
+}
+
+// how the games went for each range, next to what halving the range every time would need
+pub fn show_stats(sessions: &[Session], out: &mut dyn Write) -> io::Result<()> {
+    let mut ranges: Vec<RangeInclusive<u32>> = Vec::new();
+    for session in sessions {
+        if !ranges.contains(&session.range) {
+            ranges.push(session.range.clone());
+        }
+    }
+    ranges.sort_by_key(|range| (*range.start(), *range.end()));
+    for range in ranges {
+        let games: Vec<&Session> = sessions.iter().filter(|s| s.range == range).collect();
+        let won: Vec<&Session> = games
+            .iter()
+            .copied()
+            .filter(|s| s.result == "won")
+            .collect();
+        let size = f64::from(range.end() - range.start()) + 1.0;
+        writeln!(
+            out,
+            "{} to {}: {}, {} won",
+            range.start(),
+            range.end(),
+            plural(games.len() as u32, "game", "games"),
+            won.len()
+        )?;
+        if !won.is_empty() {
+            let average = won.iter().map(|s| f64::from(s.attempts)).sum::<f64>() / won.len() as f64;
+            let optimal = Config {
+                range: range.clone(),
+                ..Config::default()
+            }
+            .optimal_attempts();
+            writeln!(
+                out,
+                "  {average:.1} guesses on average to win, log2 of {size} is {:.1}, \
+                 halving the range every time needs at most {optimal}",
+                size.log2()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod recording_tests {
+    use super::*;
+
+    #[test]
+    fn a_recorded_game_plays_back_the_same() {
+        let config = Config::build(&["--range", "1..=8", "--seed", "21"]).unwrap();
+        let mut input = "hint\nfour\n1\n2\n3\n4\n5\n6\n7\n8\n".as_bytes();
+        let (_, transcript) = recorded_game(&mut io::sink(), &mut input, &config).unwrap();
+        let session = Session::new(&config, 0, transcript).unwrap();
+        assert_eq!(session.result, "won");
+        assert_eq!(session.steps[0].outcome, "hint");
+        assert_eq!(session.steps[1].outcome, "not a number");
+        assert_eq!(session.steps.last().unwrap().outcome, "correct");
+
+        let path = std::env::temp_dir().join(format!("recording-test-{}.log", std::process::id()));
+        session.append(&path).unwrap();
+        session.append(&path).unwrap();
+        let sessions = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(sessions, [session.clone(), session.clone()]);
+        assert_eq!(sessions[0].check(), Ok(()));
+
+        let mut out = Vec::new();
+        show_stats(&sessions, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("1 to 8: 2 games, 2 won\n"));
+        assert!(out.contains("log2 of 8 is 3.0, halving the range every time needs at most 4"));
+
+        // every enter shows the next step, and once the input ends the rest comes at once
+        let mut out = Vec::new();
+        let mut enters = "\n\n".as_bytes();
+        show_steps(&sessions[..1], &mut out, &mut enters).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.starts_with("press enter for the next step\ngame 1 of 1: from 1 to 8, seed 21\n")
+        );
+        assert!(out.contains("hint       hint\n"));
+        assert!(out.contains("\n  won after "));
+
+        // a number too big for a u32 does not wrap around
+        let line = session
+            .to_json()
+            .to_string()
+            .replace("\"end\":8", "\"end\":4294967304");
+        assert_eq!(Session::from_json(&Json::parse(&line).unwrap()), None);
+    }
+
+    #[test]
+    fn a_game_without_a_seed_is_not_recorded() {
+        let config = Config::build(&["--range", "1..=8"]).unwrap();
+        let (_, transcript) =
+            recorded_game(&mut io::sink(), &mut "quit\n".as_bytes(), &config).unwrap();
+        assert_eq!(transcript.steps[0].outcome, "quit");
+        assert_eq!(
+            Session::new(&config, 0, transcript),
+            Err("a game can only be recorded with a seed, give one with --seed".to_string())
+        );
+    }
+}
@@ -0,0 +1,462 @@
+// the guessing game for several players at once over tcp, everybody races for the same secret
+// every player gets a thread that reads their lines, the game itself sits behind a mutex,
+// and whatever happens is sent to every player, so telnet or netcat is all a player needs
+// one game per server, it closes every connection once somebody wins
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use super::{game, plural, Config};
+use crate::examples::{
+    guess::Guess,
+    prompt::{prompt, PromptError},
+};
+use game::Outcome;
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+// a player who does not read what the server sends for this long is dropped,
+// the room is locked while it writes, so one stuck player would hold up everybody
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+struct Player {
+    name: String,
+    stream: TcpStream,
+    attempts: u32,
+}
+
+struct Room {
+    game: game::GuessingGame,
+    config: Config,
+    players: HashMap<usize, Player>,
+    over: bool,
+    winner: Option<String>,
+}
+
+impl Room {
+    // a player that can not be written to is hung up on, their own thread then sees them leave
+    fn broadcast(&mut self, line: &str) {
+        for player in self.players.values_mut() {
+            if writeln!(player.stream, "{line}").is_err() {
+                player.stream.shutdown(Shutdown::Both).ok();
+            }
+        }
+    }
+
+    fn tell(&mut self, id: usize, line: &str) {
+        if let Some(player) = self.players.get_mut(&id) {
+            if writeln!(player.stream, "{line}").is_err() {
+                player.stream.shutdown(Shutdown::Both).ok();
+            }
+        }
+    }
+
+    fn finish(&mut self, winner: Option<String>) {
+        self.over = true;
+        self.winner = winner;
+    }
+
+    fn name_taken(&self, name: &str) -> bool {
+        self.players.values().any(|player| player.name == name)
+    }
+
+    fn has_attempts_left(&self, id: usize) -> bool {
+        let attempts = self.players.get(&id).map_or(0, |player| player.attempts);
+        self.config.max_attempts.is_none_or(|max| attempts < max)
+    }
+
+    // with an attempt limit the game is over once nobody playing has an attempt left
+    fn check_everyone_is_out(&mut self) {
+        if self.config.max_attempts.is_none() || self.players.is_empty() {
+            return;
+        }
+        if self.players.keys().all(|&id| !self.has_attempts_left(id)) {
+            let secret = self.game.secret();
+            self.broadcast(&format!("Nobody found it, the secret number was {secret}."));
+            self.finish(None);
+        }
+    }
+
+    fn join(&mut self, id: usize, name: &str, stream: &TcpStream) -> io::Result<()> {
+        let others = self.players.len();
+        let (low, high) = (*self.config.range.start(), *self.config.range.end());
+        self.broadcast(&format!("{name} joined."));
+        let mut stream = stream.try_clone()?;
+        writeln!(
+            stream,
+            "Hello {name}, I am thinking of a number from {low} to {high}. \
+             The first to guess it wins, {} playing already.",
+            plural(others as u32, "other is", "others are")
+        )?;
+        self.players.insert(
+            id,
+            Player {
+                name: name.to_string(),
+                stream,
+                attempts: 0,
+            },
+        );
+        Ok(())
+    }
+
+    // the players left may all be out of attempts, then the game is over
+    fn leave(&mut self, id: usize) {
+        let Some(player) = self.players.remove(&id) else {
+            return;
+        };
+        if self.over {
+            return;
+        }
+        self.broadcast(&format!("{} left.", player.name));
+        self.check_everyone_is_out();
+    }
+
+    fn guess(&mut self, id: usize, guess: u32) {
+        let max_attempts = self.config.max_attempts;
+        let Some(name) = self.players.get(&id).map(|player| player.name.clone()) else {
+            return;
+        };
+        if !self.has_attempts_left(id) {
+            self.tell(id, "You are out of attempts, wait for the others.");
+            return;
+        }
+        let answer = match self.game.guess(guess) {
+            Outcome::TooSmall => "too small",
+            Outcome::TooBig => "too big",
+            Outcome::Win { .. } => {
+                self.broadcast(&format!("{name} guessed {guess}: correct! {name} wins!"));
+                self.finish(Some(name));
+                return;
+            }
+            // play only lets guesses in the range through, and the shared game has no attempt limit
+            Outcome::OutOfRange | Outcome::OutOfAttempts { .. } => {
+                unreachable!("{guess} is a guess the shared game can not turn down")
+            }
+        };
+        let attempts = match self.players.get_mut(&id) {
+            Some(player) => {
+                player.attempts += 1;
+                player.attempts
+            }
+            None => return,
+        };
+        self.broadcast(&format!("{name} guessed {guess}: {answer}."));
+
+        if let Some(max) = max_attempts.filter(|&max| attempts >= max) {
+            self.tell(
+                id,
+                &format!(
+                    "That was your last of {}.",
+                    plural(max, "attempt", "attempts")
+                ),
+            );
+            self.check_everyone_is_out();
+        }
+    }
+}
+
+// everything one player sends, from their name to their last guess
+fn play(room: Arc<Mutex<Room>>, id: usize, mut stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    // two players with the same name could not tell who guessed what
+    let names = prompt::<String>("Welcome to the guessing game! What is your name?")
+        .default_value(format!("player {id}"))
+        .check(|name| match room.lock().unwrap().name_taken(name) {
+            true => Err(format!("{name} is playing already, pick another name")),
+            false => Ok(()),
+        });
+    loop {
+        let name = match names.ask(&mut stream, &mut reader) {
+            Ok(name) => name,
+            // hung up before giving a name, so they never join the game
+            Err(PromptError::Closed { .. }) => {
+                stream.shutdown(Shutdown::Both).ok();
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut room = room.lock().unwrap();
+        if room.over {
+            return Ok(());
+        }
+        // somebody may have taken the name since the check, now the room is locked it is certain
+        if room.name_taken(&name) {
+            writeln!(stream, "{name} is playing already, pick another name.")?;
+            continue;
+        }
+        room.join(id, &name, &stream)?;
+        break;
+    }
+
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let mut room = room.lock().unwrap();
+        if room.over {
+            return Ok(());
+        }
+        match Guess::parse(&line, room.config.range.clone()) {
+            Ok(guess) => room.guess(id, guess.value()),
+            Err(e) => room.tell(id, &format!("{e}.")),
+        }
+        if room.over {
+            return Ok(());
+        }
+    }
+
+    room.lock().unwrap().leave(id);
+    Ok(())
+}
+
+// accepts players until the game is over, and hands back the name of the winner
+// log gets a line for everyone who connects, for whoever runs the server
+pub fn serve(
+    listener: TcpListener,
+    config: &Config,
+    log: &mut dyn Write,
+) -> io::Result<Option<String>> {
+    let game = game::GuessingGame::new(
+        &Config {
+            max_attempts: None,
+            ..config.clone()
+        },
+        &mut config.rng(),
+    );
+    let room = Arc::new(Mutex::new(Room {
+        game,
+        config: config.clone(),
+        players: HashMap::new(),
+        over: false,
+        winner: None,
+    }));
+
+    // not blocking, so the loop gets to notice the game is over between players
+    listener.set_nonblocking(true)?;
+    let mut players = Vec::new();
+    let mut connections = Vec::new();
+    while !room.lock().unwrap().over {
+        match listener.accept() {
+            Ok((stream, address)) => {
+                stream.set_nonblocking(false)?;
+                stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+                writeln!(log, "{address} connected")?;
+                let room = Arc::clone(&room);
+                connections.push(stream.try_clone()?);
+                let id = players.len() + 1;
+                players.push(thread::spawn(move || play(room, id, stream)));
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(10))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    // closing the connections wakes up every thread still waiting for a line,
+    // including those of players who have not even said their name yet
+    for connection in connections {
+        connection.shutdown(Shutdown::Both).ok();
+    }
+    for player in players {
+        // a player whose connection broke does not spoil the game for the others
+        player.join().ok();
+    }
+    let winner = room.lock().unwrap().winner.clone();
+    match &winner {
+        Some(winner) => writeln!(log, "{winner} won")?,
+        None => writeln!(log, "nobody won")?,
+    }
+    Ok(winner)
+}
+
+// the bundled client: sends the name, then every line of input,
+// and prints whatever the server says until it hangs up
+// the input is sent from a thread of its own, so once the server hangs up the client is done,
+// even while that thread still waits for a line that will never be sent
+pub fn connect(
+    address: impl ToSocketAddrs,
+    name: &str,
+    mut input: Box<dyn BufRead + Send>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut stream = TcpStream::connect(address)?;
+    let server = BufReader::new(stream.try_clone()?);
+
+    writeln!(stream, "{name}")?;
+    thread::spawn(move || {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            // the server hanging up ends the game, the writes fail then
+            match input.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) if stream.write_all(line.as_bytes()).is_err() => break,
+                Ok(_) => {}
+            }
+        }
+        // done typing, the server will see this player leave and hang up
+        stream.shutdown(Shutdown::Write).ok();
+    });
+
+    for line in server.lines() {
+        writeln!(out, "{}", line?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod server_tests {
+    use std::io::{Lines, Read};
+
+    use super::*;
+
+    struct Client {
+        stream: TcpStream,
+        lines: Lines<BufReader<TcpStream>>,
+    }
+
+    impl Client {
+        fn join(address: std::net::SocketAddr, name: &str) -> Client {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .unwrap();
+            let lines = BufReader::new(stream.try_clone().unwrap()).lines();
+            writeln!(stream, "{name}").unwrap();
+            let mut client = Client { stream, lines };
+            client.wait_for("Hello");
+            client
+        }
+
+        fn send(&mut self, line: &str) {
+            writeln!(self.stream, "{line}").unwrap();
+        }
+
+        // the first line that starts with start, skipping the ones before it
+        fn wait_for(&mut self, start: &str) -> String {
+            self.lines
+                .by_ref()
+                .map(Result::unwrap)
+                .find(|line| line.starts_with(start))
+                .unwrap_or_else(|| panic!("the server never said {start:?}"))
+        }
+    }
+
+    #[test]
+    fn players_race_for_the_same_secret() {
+        let config = Config::build(&["--difficulty", "normal", "--seed", "9"]).unwrap();
+        let mut probe = game::GuessingGame::new(&config, &mut config.rng());
+        let secret = (1..=100)
+            .find(|&n| matches!(probe.guess(n), Outcome::Win { .. }))
+            .unwrap();
+        let wrong = if secret == 1 { 2 } else { 1 };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve(listener, &config, &mut io::sink()));
+
+        let mut ada = Client::join(address, "ada");
+        let mut bob = Client::join(address, "bob");
+        ada.wait_for("bob joined.");
+
+        ada.send("a lot");
+        assert_eq!(ada.wait_for("'a lot'"), "'a lot' is not a whole number.");
+        ada.send(&wrong.to_string());
+        let answer = if wrong < secret {
+            "too small"
+        } else {
+            "too big"
+        };
+        assert_eq!(
+            bob.wait_for("ada"),
+            format!("ada guessed {wrong}: {answer}.")
+        );
+        bob.send(&secret.to_string());
+        let win = format!("bob guessed {secret}: correct! bob wins!");
+        assert_eq!(ada.wait_for("bob"), win);
+        assert_eq!(bob.wait_for("bob"), win);
+
+        assert_eq!(server.join().unwrap().unwrap(), Some("bob".to_string()));
+        // the server hangs up once the game is over
+        assert!(ada.lines.next().is_none());
+    }
+
+    #[test]
+    fn leaving_can_end_the_game() {
+        let config =
+            Config::build(&["--range", "1..=100", "--attempts", "1", "--seed", "3"]).unwrap();
+        // the probe has no attempt limit, so it can try every number
+        let unlimited = Config {
+            max_attempts: None,
+            ..config.clone()
+        };
+        let mut probe = game::GuessingGame::new(&unlimited, &mut unlimited.rng());
+        let secret = (1..=100)
+            .find(|&n| matches!(probe.guess(n), Outcome::Win { .. }))
+            .unwrap();
+        let wrong = if secret == 1 { 2 } else { 1 };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve(listener, &config, &mut io::sink()));
+
+        // somebody who hangs up before giving a name does not join
+        let mut ghost = TcpStream::connect(address).unwrap();
+        ghost.shutdown(Shutdown::Write).unwrap();
+        let mut welcome = String::new();
+        ghost.read_to_string(&mut welcome).unwrap();
+        assert_eq!(
+            welcome,
+            "Welcome to the guessing game! What is your name? [player 1]\n"
+        );
+
+        let mut ada = Client::join(address, "ada");
+        let mut bob = Client::join(address, "bob");
+        ada.wait_for("bob joined.");
+
+        ada.send(&wrong.to_string());
+        assert_eq!(
+            bob.wait_for("ada"),
+            format!(
+                "ada guessed {wrong}: {}.",
+                if wrong < secret {
+                    "too small"
+                } else {
+                    "too big"
+                }
+            )
+        );
+
+        // ada is out of attempts, so bob leaving ends the game
+        drop(bob);
+        assert!(ada.wait_for("Nobody").starts_with("Nobody found it"));
+        assert_eq!(server.join().unwrap().unwrap(), None);
+    }
+
+    #[test]
+    fn the_client_stops_when_the_server_hangs_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        // input that never ends, like somebody who does not type anything
+        let idle = TcpStream::connect(address).unwrap();
+        let (_idle_end, _) = listener.accept().unwrap();
+        let server = thread::spawn(move || {
+            let (mut player, _) = listener.accept().unwrap();
+            let mut name = String::new();
+            BufReader::new(player.try_clone().unwrap())
+                .read_line(&mut name)
+                .unwrap();
+            writeln!(player, "Bye {}", name.trim()).unwrap();
+        });
+        let mut out = Vec::new();
+        connect(address, "ada", Box::new(BufReader::new(idle)), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Bye ada\n");
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+// the computer's side of the game: binary search, guessing the middle of what is left
+// it is told higher, lower or correct after every guess, by the game itself in --solver mode
+// or by a person who picked the number in --reverse mode, and people can get it wrong
+
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    // the secret is higher than the guess
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Hint {
+    pub fn parse(answer: &str) -> Option<Hint> {
+        match answer.trim().to_lowercase().as_str() {
+            "higher" | "h" | "bigger" | "+" => Some(Hint::Higher),
+            "lower" | "l" | "smaller" | "-" => Some(Hint::Lower),
+            "correct" | "c" | "yes" | "y" | "=" => Some(Hint::Correct),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solver {
+    range: RangeInclusive<u32>,
+    // where the secret can still be, given every answer so far
+    low: u32,
+    high: u32,
+    answers: Vec<(u32, Hint)>,
+}
+
+impl Solver {
+    pub fn new(range: RangeInclusive<u32>) -> Solver {
+        Solver {
+            low: *range.start(),
+            high: *range.end(),
+            range,
+            answers: Vec::new(),
+        }
+    }
+
+    // the middle of what is left, so either answer rules out half of it
+    pub fn guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    pub fn remaining(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.answers.len() as u32
+    }
+
+    // narrows the range down, or says which earlier answer this one contradicts,
+    // in which case nothing changes and the answer is not counted
+    pub fn answer(&mut self, guess: u32, hint: Hint) -> Result<(), String> {
+        match hint {
+            Hint::Higher if guess >= self.high => return Err(self.contradiction(Hint::Lower)),
+            Hint::Lower if guess <= self.low => return Err(self.contradiction(Hint::Higher)),
+            Hint::Higher => self.low = guess + 1,
+            Hint::Lower => self.high = guess - 1,
+            Hint::Correct => {}
+        }
+        self.answers.push((guess, hint));
+        Ok(())
+    }
+
+    // the earlier answer that set the bound the new one runs into
+    fn contradiction(&self, earlier: Hint) -> String {
+        let closest = self
+            .answers
+            .iter()
+            .filter(|(_, hint)| *hint == earlier)
+            .map(|(guess, _)| *guess);
+        let said = match earlier {
+            Hint::Lower => closest.min().map(|guess| format!("lower than {guess}")),
+            _ => closest.max().map(|guess| format!("higher than {guess}")),
+        };
+        match said {
+            Some(said) => format!("you said {said} earlier"),
+            None => format!(
+                "the number is from {} to {}",
+                self.range.start(),
+                self.range.end()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod solver_tests {
+    use super::*;
+    use crate::examples::guessing_game::{Config, Difficulty};
+
+    #[test]
+    fn finds_every_number_within_the_optimal_attempts() {
+        let config = Config {
+            range: Difficulty::Normal.range(),
+            ..Config::default()
+        };
+        for secret in config.range.clone() {
+            let mut solver = Solver::new(config.range.clone());
+            loop {
+                let guess = solver.guess();
+                let hint = match guess.cmp(&secret) {
+                    std::cmp::Ordering::Less => Hint::Higher,
+                    std::cmp::Ordering::Greater => Hint::Lower,
+                    std::cmp::Ordering::Equal => Hint::Correct,
+                };
+                solver.answer(guess, hint).unwrap();
+                if hint == Hint::Correct {
+                    break;
+                }
+            }
+            assert!(solver.attempts() <= config.optimal_attempts());
+        }
+    }
+
+    #[test]
+    fn contradicting_answers_are_caught() {
+        let mut solver = Solver::new(1..=100);
+        solver.answer(50, Hint::Lower).unwrap();
+        solver.answer(25, Hint::Higher).unwrap();
+        solver.answer(40, Hint::Lower).unwrap();
+        assert_eq!(solver.remaining(), 26..=39);
+        assert_eq!(
+            solver.answer(39, Hint::Higher),
+            Err("you said lower than 40 earlier".to_string())
+        );
+        assert_eq!(
+            solver.answer(26, Hint::Lower),
+            Err("you said higher than 25 earlier".to_string())
+        );
+        assert_eq!(solver.attempts(), 3);
+
+        let mut solver = Solver::new(1..=10);
+        assert_eq!(
+            solver.answer(10, Hint::Higher),
+            Err("the number is from 1 to 10".to_string())
+        );
+    }
+}
@@ -0,0 +1,423 @@
+// word games on the same loop as the number game, game_loop in mod.rs, with a secret word instead of a number
+// the words come from a text, the poem mini_grep searches unless another file is given,
+// and the secret is drawn with the same seeded generator, so --seed works here too
+// wordle says which letters are in the right place, hangman fills in the letters that were found
+
+use std::{
+    collections::BTreeSet,
+    io::{self, BufRead, Write},
+    ops::ControlFlow,
+};
+
+use rand::seq::SliceRandom;
+
+use super::{game_loop, plural, Config};
+use crate::examples::prompt::Prompt;
+
+pub const POEM: &str = include_str!("../../../poem.txt");
+
+// the attempts for wordle and the misses for hangman when --attempts does not say
+const DEFAULT_ATTEMPTS: u32 = 6;
+
+// every different word of four letters or more, in lowercase
+// words with an apostrophe, like don't, are left out rather than cut in two
+pub fn word_list(text: &str) -> Vec<String> {
+    let words: BTreeSet<String> = text
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphabetic()))
+        .filter(|word| word.chars().count() >= 4 && word.chars().all(char::is_alphabetic))
+        .map(str::to_lowercase)
+        .collect();
+    // sorted, so the same seed draws the same word
+    words.into_iter().collect()
+}
+
+fn draw(words: &[String], config: &Config) -> io::Result<String> {
+    words.choose(&mut config.rng()).cloned().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "there are no words of four letters or more to pick from",
+        )
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mark {
+    Right,
+    // in the word, but somewhere else
+    Elsewhere,
+    Absent,
+}
+
+// a letter is only marked elsewhere as often as the secret has it left over,
+// so guessing "bobby" for "abbey" marks the first b, but not the second one
+pub fn marks(secret: &str, guess: &str) -> Vec<Mark> {
+    let secret: Vec<char> = secret.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+    let mut left_over: Vec<char> = secret
+        .iter()
+        .zip(&guess)
+        .filter(|(s, g)| s != g)
+        .map(|(s, _)| *s)
+        .collect();
+    guess
+        .iter()
+        .zip(&secret)
+        .map(|(g, s)| {
+            if g == s {
+                Mark::Right
+            } else if let Some(at) = left_over.iter().position(|c| c == g) {
+                left_over.swap_remove(at);
+                Mark::Elsewhere
+            } else {
+                Mark::Absent
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WordleOutcome {
+    WrongLength { expected: usize },
+    Marks(Vec<Mark>),
+    Win { attempts: u32 },
+    // with the marks for the last guess, which are shown before the secret
+    OutOfAttempts { marks: Vec<Mark>, secret: String },
+}
+
+pub struct Wordle {
+    secret: String,
+    attempts: u32,
+    max_attempts: u32,
+}
+
+impl Wordle {
+    pub fn new(secret: String, config: &Config) -> Wordle {
+        Wordle {
+            secret,
+            attempts: 0,
+            max_attempts: config.max_attempts.unwrap_or(DEFAULT_ATTEMPTS),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.secret.chars().count()
+    }
+
+    pub fn guess(&mut self, word: &str) -> WordleOutcome {
+        let word = word.to_lowercase();
+        if word.chars().count() != self.len() {
+            return WordleOutcome::WrongLength {
+                expected: self.len(),
+            };
+        }
+        self.attempts += 1;
+        if word == self.secret {
+            WordleOutcome::Win {
+                attempts: self.attempts,
+            }
+        } else if self.attempts >= self.max_attempts {
+            WordleOutcome::OutOfAttempts {
+                marks: marks(&self.secret, &word),
+                secret: self.secret.clone(),
+            }
+        } else {
+            WordleOutcome::Marks(marks(&self.secret, &word))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HangmanOutcome {
+    // how many times the letter is in the word
+    Hit(usize),
+    Miss,
+    AlreadyGuessed,
+    Win { misses: u32 },
+    Lost { secret: String },
+}
+
+pub struct Hangman {
+    secret: String,
+    guessed: BTreeSet<char>,
+    misses: u32,
+    max_misses: u32,
+}
+
+impl Hangman {
+    pub fn new(secret: String, config: &Config) -> Hangman {
+        Hangman {
+            secret,
+            guessed: BTreeSet::new(),
+            misses: 0,
+            max_misses: config.max_attempts.unwrap_or(DEFAULT_ATTEMPTS),
+        }
+    }
+
+    // "_ o _ o _ y" for nobody with the o and the y found
+    pub fn pattern(&self) -> String {
+        let letters: Vec<String> = self
+            .secret
+            .chars()
+            .map(|c| match self.guessed.contains(&c) {
+                true => c.to_string(),
+                false => "_".to_string(),
+            })
+            .collect();
+        letters.join(" ")
+    }
+
+    pub fn misses_left(&self) -> u32 {
+        self.max_misses - self.misses
+    }
+
+    fn miss(&mut self) -> HangmanOutcome {
+        self.misses += 1;
+        if self.misses >= self.max_misses {
+            HangmanOutcome::Lost {
+                secret: self.secret.clone(),
+            }
+        } else {
+            HangmanOutcome::Miss
+        }
+    }
+
+    // a single letter, or the whole word, a wrong word costs a miss like a wrong letter
+    pub fn guess(&mut self, text: &str) -> HangmanOutcome {
+        let text = text.to_lowercase();
+        let mut letters = text.chars();
+        let (Some(letter), None) = (letters.next(), letters.next()) else {
+            if text == self.secret {
+                self.guessed.extend(self.secret.chars());
+                return HangmanOutcome::Win {
+                    misses: self.misses,
+                };
+            }
+            return self.miss();
+        };
+        if !self.guessed.insert(letter) {
+            return HangmanOutcome::AlreadyGuessed;
+        }
+        let hits = self.secret.chars().filter(|&c| c == letter).count();
+        if hits == 0 {
+            self.miss()
+        } else if self.secret.chars().all(|c| self.guessed.contains(&c)) {
+            HangmanOutcome::Win {
+                misses: self.misses,
+            }
+        } else {
+            HangmanOutcome::Hit(hits)
+        }
+    }
+}
+
+// a word, or one of the commands both games take
+#[derive(Debug, Clone)]
+enum Play {
+    Word(String),
+    GiveUp,
+    Quit,
+}
+
+fn parse_play(text: &str) -> Result<Play, String> {
+    match text {
+        "" => Err("type a guess, or giveup or quit".to_string()),
+        "giveup" => Ok(Play::GiveUp),
+        "quit" => Ok(Play::Quit),
+        word => Ok(Play::Word(word.to_string())),
+    }
+}
+
+fn show_marks(word: &str, marks: &[Mark]) -> String {
+    word.chars()
+        .zip(marks)
+        .map(|(c, mark)| match mark {
+            Mark::Right => format!("[{c}]"),
+            Mark::Elsewhere => format!("({c})"),
+            Mark::Absent => format!(" {c} "),
+        })
+        .collect()
+}
+
+pub fn wordle(
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+    config: &Config,
+    words: &[String],
+) -> io::Result<()> {
+    let mut game = Wordle::new(draw(words, config)?, config);
+    let guesses = Prompt::parse_with("Please input your guess.", parse_play);
+    writeln!(out, "Guess the word!")?;
+    writeln!(
+        out,
+        "It has {} letters and you have {}. [a] is in the right place, (a) is somewhere else.",
+        game.len(),
+        plural(game.max_attempts, "attempt", "attempts")
+    )?;
+    // the word games have no result to hand back
+    game_loop::<_, ()>(out, input, &guesses, |out, play| {
+        let guess = match play {
+            Play::Quit => {
+                writeln!(out, "Bye!")?;
+                return Ok(ControlFlow::Break(None));
+            }
+            Play::GiveUp => {
+                writeln!(out, "The secret word was {}.", game.secret)?;
+                return Ok(ControlFlow::Break(None));
+            }
+            Play::Word(word) => word,
+        };
+        match game.guess(&guess) {
+            WordleOutcome::WrongLength { expected } => {
+                writeln!(out, "The word has {expected} letters.")?
+            }
+            WordleOutcome::Marks(marks) => {
+                writeln!(out, "{}", show_marks(&guess.to_lowercase(), &marks))?
+            }
+            WordleOutcome::Win { attempts } => {
+                writeln!(
+                    out,
+                    "{}",
+                    show_marks(&game.secret, &marks(&game.secret, &game.secret))
+                )?;
+                writeln!(
+                    out,
+                    "You win! You took {}.",
+                    plural(attempts, "guess", "guesses")
+                )?;
+                return Ok(ControlFlow::Break(None));
+            }
+            WordleOutcome::OutOfAttempts { marks, secret } => {
+                writeln!(out, "{}", show_marks(&guess.to_lowercase(), &marks))?;
+                writeln!(out, "Out of attempts! The secret word was {secret}.")?;
+                return Ok(ControlFlow::Break(None));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    })?;
+    Ok(())
+}
+
+pub fn hangman(
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+    config: &Config,
+    words: &[String],
+) -> io::Result<()> {
+    let mut game = Hangman::new(draw(words, config)?, config);
+    let guesses = Prompt::parse_with("Please input your guess.", parse_play);
+    writeln!(
+        out,
+        "Guess the word one letter at a time, or all of it at once!"
+    )?;
+    let show_pattern = |out: &mut dyn Write, game: &Hangman| {
+        writeln!(
+            out,
+            "{}   ({} left)",
+            game.pattern(),
+            plural(game.misses_left(), "miss", "misses")
+        )
+    };
+    show_pattern(out, &game)?;
+    game_loop::<_, ()>(out, input, &guesses, |out, play| {
+        let guess = match play {
+            Play::Quit => {
+                writeln!(out, "Bye!")?;
+                return Ok(ControlFlow::Break(None));
+            }
+            Play::GiveUp => {
+                writeln!(out, "The secret word was {}.", game.secret)?;
+                return Ok(ControlFlow::Break(None));
+            }
+            Play::Word(word) => word,
+        };
+        match game.guess(&guess) {
+            HangmanOutcome::Hit(hits) => {
+                writeln!(out, "Yes, {}!", plural(hits as u32, "time", "times"))?
+            }
+            HangmanOutcome::Miss => writeln!(out, "No.")?,
+            HangmanOutcome::AlreadyGuessed => writeln!(out, "You guessed that one already.")?,
+            HangmanOutcome::Win { misses } => {
+                writeln!(
+                    out,
+                    "You win! The word was {}, with {}.",
+                    game.secret,
+                    plural(misses, "miss", "misses")
+                )?;
+                return Ok(ControlFlow::Break(None));
+            }
+            HangmanOutcome::Lost { secret } => {
+                writeln!(out, "Out of misses! The secret word was {secret}.")?;
+                return Ok(ControlFlow::Break(None));
+            }
+        }
+        // the word so far before every guess
+        show_pattern(out, &game)?;
+        Ok(ControlFlow::Continue(()))
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod words_tests {
+    use super::*;
+
+    #[test]
+    fn repeated_letters_are_only_marked_as_often_as_they_are_left() {
+        use Mark::*;
+        assert_eq!(
+            marks("abbey", "bobby"),
+            [Elsewhere, Absent, Right, Absent, Right]
+        );
+        assert_eq!(
+            marks("abbey", "kebab"),
+            [Absent, Elsewhere, Right, Elsewhere, Elsewhere]
+        );
+        assert_eq!(marks("frog", "goof"), [Elsewhere, Absent, Right, Elsewhere]);
+    }
+
+    #[test]
+    fn words_come_from_the_poem() {
+        let words = word_list(POEM);
+        assert!(words.contains(&"nobody".to_string()));
+        assert!(!words
+            .iter()
+            .any(|word| word.len() < 4 || word.contains('\'')));
+        // they'd is left out, not turned into they
+        assert!(!words.contains(&"they".to_string()));
+
+        let config = Config::build(&["--seed", "4"]).unwrap();
+        assert_eq!(
+            draw(&words, &config).unwrap(),
+            draw(&words, &config).unwrap()
+        );
+    }
+
+    #[test]
+    fn hangman_fills_in_the_letters() {
+        let config = Config::build(&["--attempts", "2"]).unwrap();
+        let mut game = Hangman::new("nobody".to_string(), &config);
+        assert_eq!(game.guess("o"), HangmanOutcome::Hit(2));
+        assert_eq!(game.guess("O"), HangmanOutcome::AlreadyGuessed);
+        assert_eq!(game.pattern(), "_ o _ o _ _");
+        assert_eq!(game.guess("x"), HangmanOutcome::Miss);
+        assert_eq!(game.guess("nobody"), HangmanOutcome::Win { misses: 1 });
+    }
+
+    #[test]
+    fn wordle_marks_the_letters() {
+        let config = Config::build(&["--attempts", "2"]).unwrap();
+        let mut out = Vec::new();
+        let words = ["frog".to_string()];
+        wordle(&mut out, &mut "tell\nfrog\n".as_bytes(), &config, &words).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(" t  e  l  l \n"));
+        assert!(out.ends_with("[f][r][o][g]\nYou win! You took 2 guesses.\n"));
+
+        // the last guess is marked before the secret is told
+        let mut out = Vec::new();
+        wordle(&mut out, &mut "tell\ngolf\n".as_bytes(), &config, &words).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("(g)(o) l (f)\nOut of attempts! The secret word was frog.\n"));
+    }
+}
@@ -1,5 +1,35 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct HelloWorld;
+
+impl Example for HelloWorld {
+    fn name(&self) -> &'static str {
+        "hello_world"
+    }
+
+    fn chapter(&self) -> u32 {
+        1
+    }
+
+    fn description(&self) -> &'static str {
+        "prints hello world"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        hello_world(out)?;
+        Ok(())
+    }
+}
 
 #[allow(unused)]
-pub fn hello_world() {
-    println!("hello world");
+pub fn hello_world(out: &mut dyn Write) -> io::Result<()> {
+    // writeln! works like println!, but it writes to out instead of straight to stdout
+    // that way whoever runs the example decides where the output goes
+    writeln!(out, "hello world")?;
+    Ok(())
 }
@@ -1,55 +0,0 @@
-use std::{env, fs, error::Error};
-
-struct Config {
-    query: String,
-    file_path: String,
-}
-
-impl Config {
-    fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("Not enough arguments");
-        }
-        let query = args[1].clone();
-        let file_path = args[2].clone();
-        Ok(Config { query, file_path })
-    }
-}
-
-#[cfg(test)]
-mod grep_tests {
-
-    use  super::*;
-    
-    #[test]
-    fn one_result() {
-        let query = "duct";
-        let contents = "\
-        Rust: 
-        safe, fast, productive
-        Pick three.";
-        assert_eq!(vec!["safe, fast, productive"], search(query, contents));
-    }
-
-}
-
-pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
-    let mut results = Vec::new();
-    for line in contents.lines() {
-        if line.contains(query) {
-            results.push(line);
-        }
-    }
-    results
-}
-
-
-pub fn mini_grep() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    let config = Config::build(&args)?;
-    let contents = fs::read_to_string(config.file_path)?;
-    for line in search(&config.query, &contents) {
-        println!("{line}");
-    }
-    Ok(())
-}
\ No newline at end of file
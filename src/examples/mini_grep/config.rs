@@ -0,0 +1,264 @@
+use std::{
+    env, fmt, fs,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+// the name of the project level config file
+// we look for it in the current directory and then walk up through the parents
+pub const PROJECT_FILE: &str = ".minigrep";
+
+// where a setting got its value from
+// this is what --print-config shows next to each setting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    CommandLine,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn parse(value: &str) -> Result<ColorChoice, String> {
+        match value {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(format!(
+                "invalid color '{other}', expected auto, always or never"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// a value together with the place it came from
+#[derive(Debug, Clone)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T) -> Setting<T> {
+        Setting {
+            value,
+            source: Source::Default,
+        }
+    }
+}
+
+// every option that can be set from a config file or the command line
+// config files are applied first (user, then project) and command line flags last,
+// so the most specific source always wins
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub ignore_case: Setting<bool>,
+    pub line_number: Setting<bool>,
+    pub color: Setting<ColorChoice>,
+    pub exclude_dirs: Setting<Vec<String>>,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            ignore_case: Setting::new(false),
+            line_number: Setting::new(false),
+            color: Setting::new(ColorChoice::Auto),
+            exclude_dirs: Setting::new(Vec::new()),
+        }
+    }
+}
+
+impl Settings {
+    // set a single key, config files and command line flags both end up here
+    pub fn apply(&mut self, key: &str, value: &str, source: &Source) -> Result<(), String> {
+        match key {
+            "ignore-case" => set(&mut self.ignore_case, parse_bool(value)?, source),
+            "line-number" => set(&mut self.line_number, parse_bool(value)?, source),
+            "color" => set(&mut self.color, ColorChoice::parse(value)?, source),
+            "exclude-dir" => {
+                // repeating the key within one source adds to the list,
+                // but a later source replaces the list it inherited
+                if self.exclude_dirs.source != *source {
+                    self.exclude_dirs.value.clear();
+                }
+                self.exclude_dirs.value.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|dir| !dir.is_empty())
+                        .map(String::from),
+                );
+                self.exclude_dirs.source = source.clone();
+            }
+            other => return Err(format!("unknown setting '{other}'")),
+        }
+        Ok(())
+    }
+
+    // the defaults with the user config and then the project config applied on top
+    pub fn from_config_files() -> Result<Settings, String> {
+        let mut settings = Settings::default();
+        for path in [user_config_path(), project_config_path()]
+            .into_iter()
+            .flatten()
+        {
+            settings.load_file(&path)?;
+        }
+        Ok(settings)
+    }
+
+    // read a config file made of "key = value" lines, a missing file is not an error
+    // returns whether the file existed
+    pub fn load_file(&mut self, path: &Path) -> Result<bool, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        let source = Source::File(path.to_path_buf());
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((key, value)) => self.apply(key.trim(), value.trim(), &source),
+                // a bare key is shorthand for "key = true"
+                None => self.apply(line, "true", &source),
+            };
+            result.map_err(|e| format!("{}:{}: {e}", path.display(), i + 1))?;
+        }
+        Ok(true)
+    }
+
+    // used by --print-config to show the merged settings and where each one came from
+    pub fn print(&self, out: &mut impl Write) -> io::Result<()> {
+        let exclude_dirs = self.exclude_dirs.value.join(",");
+        let rows = [
+            (
+                "ignore-case",
+                self.ignore_case.value.to_string(),
+                &self.ignore_case.source,
+            ),
+            (
+                "line-number",
+                self.line_number.value.to_string(),
+                &self.line_number.source,
+            ),
+            ("color", self.color.value.to_string(), &self.color.source),
+            ("exclude-dir", exclude_dirs, &self.exclude_dirs.source),
+        ];
+        for (key, value, source) in rows {
+            writeln!(out, "{:<24} # {source}", format!("{key} = {value}"))?;
+        }
+        Ok(())
+    }
+}
+
+fn set<T>(setting: &mut Setting<T>, value: T, source: &Source) {
+    setting.value = value;
+    setting.source = source.clone();
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        other => Err(format!("invalid boolean '{other}'")),
+    }
+}
+
+// $XDG_CONFIG_HOME/minigrep/config, falling back to ~/.config like the XDG spec says
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("minigrep").join("config"))
+}
+
+// the closest .minigrep file in the current directory or one of its parents
+pub fn project_config_path() -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    #[test]
+    fn later_sources_override_earlier_ones() {
+        let mut settings = Settings::default();
+        let file = Source::File(PathBuf::from(".minigrep"));
+        settings.apply("ignore-case", "true", &file).unwrap();
+        settings
+            .apply("ignore-case", "false", &Source::CommandLine)
+            .unwrap();
+        assert!(!settings.ignore_case.value);
+        assert_eq!(settings.ignore_case.source, Source::CommandLine);
+    }
+
+    #[test]
+    fn exclude_dirs_accumulate_within_a_source() {
+        let mut settings = Settings::default();
+        let file = Source::File(PathBuf::from(".minigrep"));
+        settings.apply("exclude-dir", "target", &file).unwrap();
+        settings
+            .apply("exclude-dir", ".git, node_modules", &file)
+            .unwrap();
+        assert_eq!(
+            settings.exclude_dirs.value,
+            vec!["target", ".git", "node_modules"]
+        );
+
+        settings
+            .apply("exclude-dir", "build", &Source::CommandLine)
+            .unwrap();
+        assert_eq!(settings.exclude_dirs.value, vec!["build"]);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_bad_values() {
+        let mut settings = Settings::default();
+        assert!(settings
+            .apply("colour", "always", &Source::CommandLine)
+            .is_err());
+        assert!(settings
+            .apply("color", "sometimes", &Source::CommandLine)
+            .is_err());
+        assert!(settings
+            .apply("ignore-case", "maybe", &Source::CommandLine)
+            .is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    io::{self, Write},
+    ops::Range,
+    path::Path,
+};
+
+// what --vimgrep prints, vim's default errorformat understands it
+pub const VIMGREP: &str = "{path}:{line}:{col}:{text}";
+
+pub const PLACEHOLDERS: &str = "\
+placeholders for --format:
+  {path}   the path of the file          {file}   just the file name
+  {line}   line number, from 1           {col}    column of the match, from 1
+  {start}  byte offset of the match in the line, from 0
+  {end}    byte offset just past the match in the line
+  {byte}   byte offset of the match in the whole file, from 0
+  {match}  the matched text              {text}   the whole line
+  {{ and }} print a literal brace";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Path,
+    File,
+    Line,
+    Col,
+    Start,
+    End,
+    Byte,
+    Match,
+    Text,
+}
+
+// an output template like "{path}:{line}:{col}: {text}", printed once per match
+// it is checked when the arguments are parsed so a typo fails before any searching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+// everything a template can ask about a single match
+pub struct Found<'a> {
+    pub path: &'a Path,
+    pub line_number: usize,
+    pub line: &'a str,
+    // where the line starts in the file
+    pub line_offset: usize,
+    pub range: Range<usize>,
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Template, String> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed '{{{name}' in --format")),
+                        }
+                    }
+                    let part = match name.as_str() {
+                        "path" => Part::Path,
+                        "file" => Part::File,
+                        "line" => Part::Line,
+                        "col" => Part::Col,
+                        "start" => Part::Start,
+                        "end" => Part::End,
+                        "byte" => Part::Byte,
+                        "match" => Part::Match,
+                        "text" => Part::Text,
+                        other => {
+                            return Err(format!(
+                                "unknown placeholder '{{{other}}}' in --format\n\n{PLACEHOLDERS}"
+                            ))
+                        }
+                    };
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(part);
+                }
+                '}' => {
+                    return Err(String::from(
+                        "unmatched '}' in --format, use '}}' for a brace",
+                    ))
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Template { parts })
+    }
+
+    pub fn render(&self, out: &mut impl Write, found: &Found) -> io::Result<()> {
+        let range = found.range.clone();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => write!(out, "{text}")?,
+                Part::Path => write!(out, "{}", found.path.display())?,
+                Part::File => {
+                    let name = found.path.file_name().unwrap_or(found.path.as_os_str());
+                    write!(out, "{}", name.to_string_lossy())?
+                }
+                Part::Line => write!(out, "{}", found.line_number)?,
+                Part::Col => write!(out, "{}", range.start + 1)?,
+                Part::Start => write!(out, "{}", range.start)?,
+                Part::End => write!(out, "{}", range.end)?,
+                Part::Byte => write!(out, "{}", found.line_offset + range.start)?,
+                Part::Match => write!(out, "{}", &found.line[range.clone()])?,
+                Part::Text => write!(out, "{}", found.line)?,
+            }
+        }
+        writeln!(out)
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn renders_every_placeholder() {
+        let template = Template::parse(
+            "{{{file}}} {path}:{line}:{col} {start}-{end} @{byte} [{match}] {text}",
+        )
+        .unwrap();
+        let found = Found {
+            path: Path::new("docs/poem.txt"),
+            line_number: 3,
+            line: "Then there's a pair of us",
+            line_offset: 40,
+            range: 15..19,
+        };
+        let mut out = Vec::new();
+        template.render(&mut out, &found).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{poem.txt} docs/poem.txt:3:16 15-19 @55 [pair] Then there's a pair of us\n"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        let err = Template::parse("{path}:{lines}").unwrap_err();
+        assert!(err.starts_with("unknown placeholder '{lines}'"));
+        assert!(Template::parse("{path").is_err());
+        assert!(Template::parse("oops}").is_err());
+    }
+}
@@ -0,0 +1,609 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    error::Error,
+    fs,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use super::{collect_files, config::Source, Settings};
+
+// the index lives next to the files it describes
+pub const INDEX_FILE: &str = ".minigrep-index";
+const HEADER: &str = "minigrep-index 1";
+
+pub const INDEX_USAGE: &str = "\
+usage: mini_grep index [--exclude-dir <dir>] [--no-config] [<dir>]
+
+builds or updates the word index for <dir> (the current directory by default)";
+
+pub const QUERY_USAGE: &str = "\
+usage: mini_grep query [--dir <dir>] <word>...
+
+answers a word or phrase query from the index in <dir>, updating it first if files changed";
+
+// a file the index knows about, the path is relative to the index root
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileEntry {
+    path: PathBuf,
+    modified: u128,
+    len: u64,
+}
+
+// one line of one file containing a word, and how many times the word shows up on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Posting {
+    file: usize,
+    line: usize,
+    count: usize,
+}
+
+#[derive(Debug)]
+pub struct Index {
+    root: PathBuf,
+    exclude_dirs: Vec<String>,
+    files: Vec<FileEntry>,
+    words: HashMap<String, Vec<Posting>>,
+}
+
+// what had to be done to bring the index up to date
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Update {
+    pub added: usize,
+    pub changed: usize,
+    pub removed: usize,
+}
+
+impl Update {
+    pub fn is_empty(&self) -> bool {
+        *self == Update::default()
+    }
+}
+
+// a line answering a query
+#[derive(Debug, PartialEq, Eq)]
+pub struct Hit {
+    pub path: PathBuf,
+    pub line_number: usize,
+    pub text: String,
+    count: usize,
+}
+
+// we split on whitespace like the word counting example in collections.rs,
+// then trim punctuation and lowercase so "World," and "world" end up as the same word
+pub fn words(line: &str) -> impl Iterator<Item = String> + '_ {
+    line.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+}
+
+impl Index {
+    pub fn new(root: &Path, exclude_dirs: Vec<String>) -> Index {
+        Index {
+            root: root.to_path_buf(),
+            exclude_dirs,
+            files: Vec::new(),
+            words: HashMap::new(),
+        }
+    }
+
+    fn file_path(root: &Path) -> PathBuf {
+        root.join(INDEX_FILE)
+    }
+
+    // read the index for root, or None if it has never been built
+    pub fn load(root: &Path) -> Result<Option<Index>, Box<dyn Error>> {
+        let path = Index::file_path(root);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("{}: {e}", path.display()).into()),
+        };
+        let corrupt = |line: usize| {
+            format!(
+                "{}:{line}: corrupt index, run `rust-book-examples grep index` to rebuild it",
+                path.display()
+            )
+        };
+
+        let mut lines = contents.lines().enumerate();
+        if lines.next().map(|(_, header)| header) != Some(HEADER) {
+            return Err(corrupt(1).into());
+        }
+        let mut index = Index::new(root, Vec::new());
+        for (i, line) in lines {
+            let mut fields = line.split('\t');
+            match fields.next() {
+                Some("exclude") => {
+                    let dir = fields.next().and_then(unescape);
+                    index.exclude_dirs.push(dir.ok_or_else(|| corrupt(i + 1))?);
+                }
+                Some("file") => {
+                    let mut field = || fields.next().ok_or_else(|| corrupt(i + 1));
+                    let modified = field()?.parse().map_err(|_| corrupt(i + 1))?;
+                    let len = field()?.parse().map_err(|_| corrupt(i + 1))?;
+                    let path = unescape(field()?).ok_or_else(|| corrupt(i + 1))?;
+                    let path = PathBuf::from(path);
+                    index.files.push(FileEntry {
+                        path,
+                        modified,
+                        len,
+                    });
+                }
+                Some("word") => {
+                    let word = fields.next().ok_or_else(|| corrupt(i + 1))?;
+                    let postings = fields
+                        .next()
+                        .ok_or_else(|| corrupt(i + 1))?
+                        .split(' ')
+                        .map(|posting| parse_posting(posting, index.files.len()))
+                        .collect::<Option<Vec<Posting>>>()
+                        .ok_or_else(|| corrupt(i + 1))?;
+                    index.words.insert(word.to_string(), postings);
+                }
+                _ => return Err(corrupt(i + 1).into()),
+            }
+        }
+        Ok(Some(index))
+    }
+
+    // written to a temporary file first so a crash never leaves half an index behind
+    pub fn save(&self) -> io::Result<()> {
+        let path = Index::file_path(&self.root);
+        let tmp = path.with_extension("tmp");
+        let mut out = io::BufWriter::new(fs::File::create(&tmp)?);
+        writeln!(out, "{HEADER}")?;
+        for dir in &self.exclude_dirs {
+            writeln!(out, "exclude\t{}", escape(dir))?;
+        }
+        for file in &self.files {
+            writeln!(
+                out,
+                "file\t{}\t{}\t{}",
+                file.modified,
+                file.len,
+                escape(&file.path.to_string_lossy())
+            )?;
+        }
+        let mut words: Vec<_> = self.words.iter().collect();
+        words.sort_by(|a, b| a.0.cmp(b.0));
+        for (word, postings) in words {
+            let postings: Vec<String> = postings
+                .iter()
+                .map(|p| format!("{}:{}:{}", p.file, p.line, p.count))
+                .collect();
+            writeln!(out, "word\t{word}\t{}", postings.join(" "))?;
+        }
+        out.into_inner()?.sync_all()?;
+        fs::rename(tmp, path)
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    // reindex files that were added or changed since the last update and forget deleted ones
+    pub fn update(&mut self) -> io::Result<Update> {
+        let mut update = Update::default();
+        let known: HashMap<PathBuf, usize> = self
+            .files
+            .iter()
+            .enumerate()
+            .map(|(id, file)| (file.path.clone(), id))
+            .collect();
+        let mut seen = HashSet::new();
+        let mut stale = HashSet::new();
+        let mut fresh = Vec::new();
+
+        for path in collect_files(&[&self.root], &self.exclude_dirs)? {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if name.starts_with(INDEX_FILE) {
+                continue;
+            }
+            let metadata = fs::metadata(&path)?;
+            let modified = metadata
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos());
+            let relative = path.strip_prefix(&self.root).unwrap_or(&path).to_path_buf();
+            match known.get(&relative) {
+                Some(&id) => {
+                    seen.insert(id);
+                    let file = &self.files[id];
+                    if file.modified == modified && file.len == metadata.len() {
+                        continue;
+                    }
+                    stale.insert(id);
+                    update.changed += 1;
+                }
+                None => update.added += 1,
+            }
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                // binary files are remembered, so they are not added again next time,
+                // but none of their words are indexed
+                Err(e) if e.kind() == ErrorKind::InvalidData => String::new(),
+                Err(e) => return Err(e),
+            };
+            let file = FileEntry {
+                path: relative,
+                modified,
+                len: metadata.len(),
+            };
+            fresh.push((file, contents));
+        }
+
+        for id in 0..self.files.len() {
+            if !seen.contains(&id) {
+                stale.insert(id);
+                update.removed += 1;
+            }
+        }
+        self.remove_files(&stale);
+        for (file, contents) in fresh {
+            self.add_file(file, &contents);
+        }
+        Ok(update)
+    }
+
+    fn add_file(&mut self, file: FileEntry, contents: &str) {
+        let id = self.files.len();
+        self.files.push(file);
+        for (i, line) in contents.lines().enumerate() {
+            // the same word counting as the HashMap example in collections.rs, one line at a time
+            let mut counts = HashMap::new();
+            for word in words(line) {
+                let count = counts.entry(word).or_insert(0);
+                *count += 1;
+            }
+            for (word, count) in counts {
+                self.words.entry(word).or_default().push(Posting {
+                    file: id,
+                    line: i + 1,
+                    count,
+                });
+            }
+        }
+    }
+
+    // drop the postings of the given files and renumber the rest so ids stay dense
+    fn remove_files(&mut self, ids: &HashSet<usize>) {
+        if ids.is_empty() {
+            return;
+        }
+        let mut next = 0;
+        let renumbered: Vec<Option<usize>> = (0..self.files.len())
+            .map(|id| {
+                if ids.contains(&id) {
+                    None
+                } else {
+                    next += 1;
+                    Some(next - 1)
+                }
+            })
+            .collect();
+        let mut id = 0;
+        self.files.retain(|_| {
+            id += 1;
+            renumbered[id - 1].is_some()
+        });
+        for postings in self.words.values_mut() {
+            postings.retain_mut(|posting| match renumbered[posting.file] {
+                Some(id) => {
+                    posting.file = id;
+                    true
+                }
+                None => false,
+            });
+        }
+        self.words.retain(|_, postings| !postings.is_empty());
+    }
+
+    // a single word is answered from the index alone, for a phrase the index narrows
+    // things down to lines with every word and then we check the words are next to each other
+    pub fn query(&self, query: &str) -> io::Result<Vec<Hit>> {
+        let terms: Vec<String> = words(query).collect();
+        let mut candidates = match terms.first().and_then(|term| self.words.get(term)) {
+            Some(postings) => postings.clone(),
+            None => return Ok(Vec::new()),
+        };
+        for term in &terms[1..] {
+            let lines: HashSet<(usize, usize)> = match self.words.get(term) {
+                Some(postings) => postings.iter().map(|p| (p.file, p.line)).collect(),
+                None => return Ok(Vec::new()),
+            };
+            candidates.retain(|p| lines.contains(&(p.file, p.line)));
+        }
+
+        // every file with a hit is read and split into lines once, not once per posting
+        let mut lines: HashMap<usize, Vec<String>> = HashMap::new();
+        let mut hits = Vec::new();
+        for posting in candidates {
+            let path = self.root.join(&self.files[posting.file].path);
+            if let Entry::Vacant(entry) = lines.entry(posting.file) {
+                let contents = fs::read_to_string(&path)?;
+                entry.insert(contents.lines().map(str::to_string).collect());
+            }
+            let text = lines[&posting.file]
+                .get(posting.line - 1)
+                .map_or("", String::as_str);
+            if terms.len() > 1 {
+                let line_words: Vec<String> = words(text).collect();
+                if !line_words.windows(terms.len()).any(|w| w == terms) {
+                    continue;
+                }
+            }
+            hits.push(Hit {
+                path,
+                line_number: posting.line,
+                text: text.to_string(),
+                count: posting.count,
+            });
+        }
+
+        // files where the query shows up most often come first
+        let mut scores: HashMap<PathBuf, usize> = HashMap::new();
+        for hit in &hits {
+            let score = scores.entry(hit.path.clone()).or_insert(0);
+            *score += hit.count;
+        }
+        hits.sort_by(|a, b| {
+            scores[&b.path]
+                .cmp(&scores[&a.path])
+                .then_with(|| a.path.cmp(&b.path))
+                .then(a.line_number.cmp(&b.line_number))
+        });
+        Ok(hits)
+    }
+}
+
+// the index is one record per line with tabs between the fields,
+// so a tab, a newline or a backslash in a path is written as \t, \n or \\
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => unescaped.push('\\'),
+            't' => unescaped.push('\t'),
+            'n' => unescaped.push('\n'),
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
+
+fn parse_posting(posting: &str, files: usize) -> Option<Posting> {
+    let mut parts = posting.split(':').map(|part| part.parse::<usize>().ok());
+    let posting = Posting {
+        file: parts.next()??,
+        line: parts.next()??,
+        count: parts.next()??,
+    };
+    (posting.file < files && posting.line > 0).then_some(posting)
+}
+
+// mini_grep index [--exclude-dir <dir>] [--no-config] [<dir>]
+pub fn index_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut root = None;
+    let mut use_config_files = true;
+    let mut exclude_dirs = Vec::new();
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-config" => use_config_files = false,
+            "--exclude-dir" => {
+                let dir = args.next().ok_or("--exclude-dir needs a value")?;
+                exclude_dirs.push(dir.clone());
+            }
+            "-h" | "--help" => return Err(INDEX_USAGE.into()),
+            flag if flag.starts_with('-') => {
+                return Err(format!("unknown option '{flag}'\n\n{INDEX_USAGE}").into())
+            }
+            dir if root.is_none() => root = Some(PathBuf::from(dir)),
+            _ => return Err(INDEX_USAGE.into()),
+        }
+    }
+    let root = root.unwrap_or_else(|| PathBuf::from("."));
+
+    // excluded directories come from the config files like a normal search
+    let mut settings = if use_config_files {
+        Settings::from_config_files()?
+    } else {
+        Settings::default()
+    };
+    for dir in exclude_dirs {
+        settings.apply("exclude-dir", &dir, &Source::CommandLine)?;
+    }
+
+    let mut index = match Index::load(&root)? {
+        Some(index) => index,
+        None => Index::new(&root, Vec::new()),
+    };
+    index.exclude_dirs = settings.exclude_dirs.value;
+    let update = index.update()?;
+    index.save()?;
+    println!(
+        "indexed {} files ({} added, {} changed, {} removed), {} distinct words",
+        index.file_count(),
+        update.added,
+        update.changed,
+        update.removed,
+        index.word_count()
+    );
+    Ok(())
+}
+
+// mini_grep query [--dir <dir>] <word>...
+pub fn query_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut root = PathBuf::from(".");
+    let mut terms = Vec::new();
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dir" => root = PathBuf::from(args.next().ok_or("--dir needs a value")?),
+            "-h" | "--help" => return Err(QUERY_USAGE.into()),
+            "--" => terms.extend(args.by_ref().cloned()),
+            flag if flag.starts_with('-') => {
+                return Err(format!("unknown option '{flag}'\n\n{QUERY_USAGE}").into())
+            }
+            term => terms.push(term.to_string()),
+        }
+    }
+    if terms.is_empty() {
+        return Err(QUERY_USAGE.into());
+    }
+
+    let mut index = Index::load(&root)?.ok_or_else(|| {
+        format!(
+            "no index in {}, run `rust-book-examples grep index {}` first",
+            root.display(),
+            root.display()
+        )
+    })?;
+    if !index.update()?.is_empty() {
+        index.save()?;
+    }
+    let mut out = io::stdout().lock();
+    for hit in index.query(&terms.join(" "))? {
+        writeln!(
+            out,
+            "{}:{}:{}",
+            hit.path.display(),
+            hit.line_number,
+            hit.text
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod index_tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("minigrep-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn lines(hits: &[Hit]) -> Vec<(String, usize)> {
+        hits.iter()
+            .map(|hit| {
+                let name = hit.path.file_name().unwrap().to_string_lossy();
+                (name.into_owned(), hit.line_number)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn answers_words_and_phrases() {
+        let dir = scratch_dir("phrases");
+        fs::write(dir.join("a.txt"), "hello world\nwonderful world, hello").unwrap();
+        fs::write(dir.join("b.txt"), "World world WORLD").unwrap();
+        let mut index = Index::new(&dir, Vec::new());
+        index.update().unwrap();
+
+        // b.txt has the most worlds so it is ranked first
+        let hits = index.query("world").unwrap();
+        assert_eq!(
+            lines(&hits),
+            vec![
+                ("b.txt".into(), 1),
+                ("a.txt".into(), 1),
+                ("a.txt".into(), 2)
+            ]
+        );
+        assert_eq!(
+            lines(&index.query("hello world").unwrap()),
+            vec![("a.txt".into(), 1)]
+        );
+        assert!(index.query("world wonderful").unwrap().is_empty());
+        assert!(index.query("missing").unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn updates_incrementally_and_survives_a_reload() {
+        let dir = scratch_dir("incremental");
+        fs::write(dir.join("a.txt"), "apple banana").unwrap();
+        fs::write(dir.join("b.txt"), "banana cherry").unwrap();
+        let mut index = Index::new(&dir, Vec::new());
+        let update = index.update().unwrap();
+        assert_eq!(update.added, 2);
+        index.save().unwrap();
+
+        fs::remove_file(dir.join("a.txt")).unwrap();
+        fs::write(dir.join("b.txt"), "banana cherry\ndurian").unwrap();
+        fs::write(dir.join("c.txt"), "elderberry").unwrap();
+        let mut index = Index::load(&dir).unwrap().unwrap();
+        let update = index.update().unwrap();
+        assert_eq!(
+            update,
+            Update {
+                added: 1,
+                changed: 1,
+                removed: 1
+            }
+        );
+        assert!(index.query("apple").unwrap().is_empty());
+        assert_eq!(
+            lines(&index.query("durian").unwrap()),
+            vec![("b.txt".into(), 2)]
+        );
+        assert_eq!(
+            lines(&index.query("elderberry").unwrap()),
+            vec![("c.txt".into(), 1)]
+        );
+        assert!(index.update().unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn binary_files_and_odd_paths_are_only_added_once() {
+        let dir = scratch_dir("odd");
+        fs::write(dir.join("image.bin"), [0xff, 0xfe, 0x00]).unwrap();
+        fs::write(dir.join("tab\there\\.txt"), "fig\n").unwrap();
+        let mut index = Index::new(&dir, vec!["new\nline".to_string()]);
+        assert_eq!(index.update().unwrap().added, 2);
+        index.save().unwrap();
+
+        let mut index = Index::load(&dir).unwrap().unwrap();
+        assert_eq!(index.exclude_dirs, ["new\nline"]);
+        assert!(index.update().unwrap().is_empty());
+        assert_eq!(
+            lines(&index.query("fig").unwrap()),
+            vec![("tab\there\\.txt".into(), 1)]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,480 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+mod config;
+mod format;
+mod index;
+mod repl;
+mod stats;
+
+use super::Example;
+use config::{ColorChoice, Settings, Source};
+use format::{Found, Template};
+use stats::{Stats, StatsFormat};
+
+pub use index::{index_command, query_command};
+
+const USAGE: &str = "\
+usage: mini_grep [options] <query> <path>...
+       mini_grep [options] --interactive <path>...
+       mini_grep index [--exclude-dir <dir>] [--no-config] [<dir>]
+       mini_grep query [--dir <dir>] <word>...
+
+options:
+  -i, --ignore-case        match without caring about upper or lower case
+      --no-ignore-case     match case exactly
+  -n, --line-number        print the line number of each match
+      --no-line-number     do not print line numbers
+      --color <when>       highlight matches: auto, always or never
+      --exclude-dir <dir>  skip directories with this name (can be repeated)
+      --no-config          ignore the user and project config files
+      --print-config       show the merged settings and where they came from
+  -I, --interactive        load the files once and read queries from stdin
+      --stats[=<format>]   print a summary of the search to stderr: text (default) or json
+      --vimgrep            print every match as path:line:col:text for editors to jump to
+      --format <template>  print every match using a template like '{path}:{line}:{col}: {text}'
+
+defaults are read from $XDG_CONFIG_HOME/minigrep/config and the closest .minigrep file
+`index` builds a word index for faster searches and `query` searches it,
+to search for the word index or query itself put -- before it: mini_grep -- index <path>";
+
+// the full help text, including the --format placeholders
+pub fn usage() -> String {
+    format!("{USAGE}\n\n{}", format::PLACEHOLDERS)
+}
+
+struct Config {
+    query: String,
+    paths: Vec<String>,
+    settings: Settings,
+    print_config: bool,
+    stats: Option<StatsFormat>,
+    interactive: bool,
+    // set by --vimgrep or --format, prints one line per match instead of per matching line
+    template: Option<Template>,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, String> {
+        let mut positional = Vec::new();
+        // flags are collected as key/value pairs so they can be applied after the config files
+        let mut overrides: Vec<(&str, String)> = Vec::new();
+        let mut use_config_files = true;
+        let mut print_config = false;
+        let mut stats = None;
+        let mut interactive = false;
+        let mut template = None;
+
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            // flags can be written as "--color always" or "--color=always"
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value_for = |flag: &str| {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or(format!("{flag} needs a value"))
+            };
+            match flag {
+                "-i" | "--ignore-case" => overrides.push(("ignore-case", "true".into())),
+                "--no-ignore-case" => overrides.push(("ignore-case", "false".into())),
+                "-n" | "--line-number" => overrides.push(("line-number", "true".into())),
+                "--no-line-number" => overrides.push(("line-number", "false".into())),
+                "--color" | "--colour" => overrides.push(("color", value_for(flag)?)),
+                "--exclude-dir" => overrides.push(("exclude-dir", value_for(flag)?)),
+                "-I" | "--interactive" => interactive = true,
+                "--no-config" => use_config_files = false,
+                "--print-config" => print_config = true,
+                // the format is optional, so it is only taken from "--stats=json"
+                "--stats" => {
+                    let format = inline_value.as_deref().unwrap_or("text");
+                    stats = Some(StatsFormat::parse(format)?);
+                }
+                "--vimgrep" => template = Some(Template::parse(format::VIMGREP)?),
+                "--format" => template = Some(Template::parse(&value_for(flag)?)?),
+                "-h" | "--help" => return Err(usage()),
+                "--" => positional.extend(args.by_ref().cloned()),
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(format!("unknown option '{flag}'\n\n{USAGE}"))
+                }
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        let mut settings = if use_config_files {
+            Settings::from_config_files()?
+        } else {
+            Settings::default()
+        };
+        for (key, value) in overrides {
+            settings.apply(key, &value, &Source::CommandLine)?;
+        }
+
+        // --print-config does not search, so it does not need a query or paths
+        // and in interactive mode the queries come from stdin instead
+        let needed = if interactive { 1 } else { 2 };
+        if positional.len() < needed && !print_config {
+            return Err(format!("Not enough arguments\n\n{USAGE}"));
+        }
+        let query = if positional.is_empty() || interactive {
+            String::new()
+        } else {
+            positional.remove(0)
+        };
+        Ok(Config {
+            query,
+            paths: positional,
+            settings,
+            print_config,
+            stats,
+            interactive,
+            template,
+        })
+    }
+
+    fn use_color(&self) -> bool {
+        match self.settings.color.value {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => io::stdout().is_terminal(),
+        }
+    }
+
+    // results are prefixed with their file once there is more than one file to look at
+    fn show_paths(&self) -> bool {
+        self.paths.len() > 1 || self.paths.iter().any(|p| Path::new(p).is_dir())
+    }
+}
+
+#[cfg(test)]
+mod grep_tests {
+
+    use  super::*;
+    
+    #[test]
+    fn one_result() {
+        let query = "duct";
+        let contents = "\
+        Rust: 
+        safe, fast, productive
+        Pick three.";
+        assert_eq!(vec!["safe, fast, productive"], search(query, contents));
+    }
+
+    #[test]
+    fn case_insensitive_ranges() {
+        assert_eq!(
+            match_ranges("rUsT", "Rust and rust", true),
+            vec![0..4, 9..13]
+        );
+        assert_eq!(
+            match_ranges("rUsT", "Rust and rust", false),
+            Vec::<Range<usize>>::new()
+        );
+        assert_eq!(match_ranges("é", "café CAFÉ", true), vec![3..5, 9..11]);
+    }
+
+    #[test]
+    fn flags_override_config() {
+        let args: Vec<String> = [
+            "mini_grep",
+            "--no-config",
+            "-i",
+            "--exclude-dir=target",
+            "to",
+            "src",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let config = Config::build(&args).unwrap();
+        assert_eq!(config.query, "to");
+        assert_eq!(config.paths, vec!["src"]);
+        assert!(config.settings.ignore_case.value);
+        assert_eq!(config.settings.exclude_dirs.value, vec!["target"]);
+    }
+
+    #[test]
+    fn byte_offsets_count_line_endings() {
+        let args: Vec<String> = [
+            "mini_grep",
+            "--no-config",
+            "--format",
+            "{line}@{byte}",
+            "to",
+            "x",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let config = Config::build(&args).unwrap();
+        let documents = [Document {
+            path: PathBuf::from("x"),
+            contents: "a to\r\nto\n\nb to".to_string(),
+        }];
+        let mut out = Vec::new();
+        print_matches(&mut out, &config, "to", &documents, &mut Stats::default()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "1@2\n2@6\n4@12\n");
+    }
+}
+
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let mut results = Vec::new();
+    for line in contents.lines() {
+        if line.contains(query) {
+            results.push(line);
+        }
+    }
+    results
+}
+
+// the byte ranges of every non overlapping match of query in line
+// we compare char by char when ignoring case so the ranges always point into the original line
+pub fn match_ranges(query: &str, line: &str, ignore_case: bool) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    if query.is_empty() {
+        return ranges;
+    }
+    let mut start = 0;
+    while let Some(c) = line[start..].chars().next() {
+        match match_len(query, &line[start..], ignore_case) {
+            Some(len) => {
+                ranges.push(start..start + len);
+                start += len;
+            }
+            None => start += c.len_utf8(),
+        }
+    }
+    ranges
+}
+
+// how many bytes of haystack the query matches, if it matches at the very start
+fn match_len(query: &str, haystack: &str, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return haystack.starts_with(query).then_some(query.len());
+    }
+    let mut chars = haystack.char_indices();
+    for q in query.chars() {
+        let (_, h) = chars.next()?;
+        if !h.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(haystack.len(), |(i, _)| i))
+}
+
+// expand the paths from the command line, walking into directories
+fn collect_files<P: AsRef<Path>>(paths: &[P], exclude_dirs: &[String]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            walk(path, exclude_dirs, &mut files)?;
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+fn walk(dir: &Path, exclude_dirs: &[String], files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if !exclude_dirs.iter().any(|dir| dir == name) {
+                walk(&path, exclude_dirs, files)?;
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+const MATCH_COLOR: &str = "\x1b[1;31m";
+const PATH_COLOR: &str = "\x1b[35m";
+const RESET: &str = "\x1b[0m";
+
+fn print_line(
+    out: &mut impl Write,
+    config: &Config,
+    path: Option<&Path>,
+    line_number: usize,
+    line: &str,
+    ranges: &[Range<usize>],
+    color: bool,
+) -> io::Result<()> {
+    if let Some(path) = path {
+        if color {
+            write!(out, "{PATH_COLOR}{}{RESET}:", path.display())?;
+        } else {
+            write!(out, "{}:", path.display())?;
+        }
+    }
+    if config.settings.line_number.value {
+        write!(out, "{line_number}:")?;
+    }
+    if !color {
+        return writeln!(out, "{line}");
+    }
+    let mut last = 0;
+    for range in ranges {
+        write!(
+            out,
+            "{}{MATCH_COLOR}{}{RESET}",
+            &line[last..range.start],
+            &line[range.clone()]
+        )?;
+        last = range.end;
+    }
+    writeln!(out, "{}", &line[last..])
+}
+
+// a file read into memory so it can be searched more than once
+struct Document {
+    path: PathBuf,
+    contents: String,
+}
+
+fn load(config: &Config, stats: &mut Stats) -> Result<Vec<Document>, Box<dyn Error>> {
+    let mut documents = Vec::new();
+    for path in collect_files(&config.paths, &config.settings.exclude_dirs.value)? {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            // binary files found while walking a directory are skipped quietly
+            Err(e) if config.show_paths() && e.kind() == io::ErrorKind::InvalidData => continue,
+            Err(e) => return Err(format!("{}: {e}", path.display()).into()),
+        };
+        stats.files_searched += 1;
+        stats.bytes_read += contents.len();
+        documents.push(Document { path, contents });
+    }
+    Ok(documents)
+}
+
+// print every line of the documents that matches query, returns how many lines matched
+fn print_matches(
+    out: &mut impl Write,
+    config: &Config,
+    query: &str,
+    documents: &[Document],
+    stats: &mut Stats,
+) -> io::Result<usize> {
+    let color = config.use_color();
+    let show_paths = config.show_paths();
+    let mut matched_lines = 0;
+    for document in documents {
+        let mut matched = false;
+        // where the next line starts in the file, counting the line endings lines() leaves out
+        let mut next_offset = 0;
+        for (i, full_line) in document.contents.split_inclusive('\n').enumerate() {
+            let line_offset = next_offset;
+            next_offset += full_line.len();
+            let line = full_line.strip_suffix('\n').unwrap_or(full_line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            stats.lines_scanned += 1;
+            let ranges = match_ranges(query, line, config.settings.ignore_case.value);
+            if ranges.is_empty() {
+                continue;
+            }
+            matched = true;
+            matched_lines += 1;
+            stats.matches += ranges.len();
+            if let Some(template) = &config.template {
+                for range in ranges {
+                    let found = Found {
+                        path: &document.path,
+                        line_number: i + 1,
+                        line,
+                        line_offset,
+                        range,
+                    };
+                    template.render(out, &found)?;
+                }
+                continue;
+            }
+            let prefix = show_paths.then_some(document.path.as_path());
+            print_line(out, config, prefix, i + 1, line, &ranges, color)?;
+        }
+        if matched {
+            stats.files_matched += 1;
+        }
+    }
+    Ok(matched_lines)
+}
+
+// the run from the book: searching poem.txt for "to"
+pub struct MiniGrep;
+
+impl Example for MiniGrep {
+    fn name(&self) -> &'static str {
+        "mini_grep"
+    }
+
+    fn chapter(&self) -> u32 {
+        12
+    }
+
+    fn description(&self) -> &'static str {
+        "searching poem.txt for \"to\" with the grep clone from chapter 12"
+    }
+
+    fn run(&self, out: &mut dyn Write, input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        let args = [
+            "mini_grep",
+            "--no-config",
+            "--color=never",
+            "to",
+            "poem.txt",
+        ];
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        mini_grep_with(&args, out, input)
+    }
+}
+
+// args[0] is the name we were called by, like env::args() in the book
+pub fn mini_grep(args: &[String]) -> Result<(), Box<dyn Error>> {
+    mini_grep_with(args, &mut io::stdout().lock(), &mut io::stdin().lock())
+}
+
+fn mini_grep_with(
+    args: &[String],
+    mut out: &mut dyn Write,
+    mut input: &mut dyn BufRead,
+) -> Result<(), Box<dyn Error>> {
+    let mut config = Config::build(args)?;
+    if config.print_config {
+        config.settings.print(&mut out)?;
+        return Ok(());
+    }
+    let started = Instant::now();
+    let mut stats = Stats::default();
+    let documents = load(&config, &mut stats)?;
+    if config.interactive {
+        repl::repl(&mut config, &documents, &mut input, &mut out, &mut stats)?;
+    } else {
+        print_matches(&mut out, &config, &config.query, &documents, &mut stats)?;
+    }
+    stats.elapsed = started.elapsed();
+    if let Some(format) = config.stats {
+        out.flush()?;
+        stats.report(format, &mut io::stderr())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::io::{self, BufRead, Write};
+
+use super::{print_matches, Config, Document, Stats};
+use crate::examples::prompt::{Prompt, PromptError};
+
+const COMMANDS: &str = "\
+:i      toggle ignore case
+:n      toggle line numbers
+:count  toggle printing only the number of matching lines
+:help   show this list
+:quit   leave (so does ctrl-d)";
+
+// read queries line by line with a Prompt, the same way guessing_game reads guesses,
+// and search the documents that were loaded once up front
+pub fn repl(
+    config: &mut Config,
+    documents: &[Document],
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    stats: &mut Stats,
+) -> io::Result<()> {
+    let mut count_only = false;
+    writeln!(
+        out,
+        "loaded {} file(s), type a query or :help",
+        documents.len()
+    )?;
+    // every line is a query or a command, so nothing is turned down,
+    // the spaces around a query are part of what is searched for
+    let queries = Prompt::parse_with(">", |text| Ok(text.to_string()))
+        .inline()
+        .untrimmed();
+    loop {
+        let query = match queries.ask(out, input) {
+            Ok(query) => query,
+            // the end of stdin ends the session, on a line of its own
+            Err(PromptError::Closed { .. }) => return writeln!(out),
+            Err(e) => return Err(e.into()),
+        };
+
+        // a command is known with spaces around it too
+        match query.trim() {
+            "" => continue,
+            ":q" | ":quit" => return Ok(()),
+            ":h" | ":help" => writeln!(out, "{COMMANDS}")?,
+            ":i" => {
+                let ignore_case = &mut config.settings.ignore_case.value;
+                *ignore_case = !*ignore_case;
+                writeln!(out, "ignore case: {}", on_off(*ignore_case))?;
+            }
+            ":n" => {
+                let line_number = &mut config.settings.line_number.value;
+                *line_number = !*line_number;
+                writeln!(out, "line numbers: {}", on_off(*line_number))?;
+            }
+            ":count" => {
+                count_only = !count_only;
+                writeln!(out, "count only: {}", on_off(count_only))?;
+            }
+            command if command.starts_with(':') => {
+                writeln!(out, "unknown command '{command}', try :help")?;
+            }
+            _ if count_only => {
+                let matched = print_matches(&mut io::sink(), config, &query, documents, stats)?;
+                writeln!(out, "{matched}")?;
+            }
+            _ => {
+                print_matches(out, config, &query, documents, stats)?;
+            }
+        }
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+#[cfg(test)]
+mod repl_tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn toggles_apply_to_following_queries() {
+        let args: Vec<String> = [
+            "mini_grep",
+            "--no-config",
+            "--color=never",
+            "-I",
+            "poem.txt",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut config = Config::build(&args).unwrap();
+        let documents = vec![Document {
+            path: PathBuf::from("poem.txt"),
+            contents: String::from("Then there's a pair of us\nThey'd banish us\nthe end"),
+        }];
+        let mut input = "the\n:i\n:n\n the\n:count\nthe\n :quit \nnever read\n".as_bytes();
+        let mut out = Vec::new();
+        let mut stats = Stats::default();
+        repl(&mut config, &documents, &mut input, &mut out, &mut stats).unwrap();
+
+        // " the" is searched with its space, so "the end" at the start of a line is left out
+
+        let out = String::from_utf8(out).unwrap();
+        let expected = "\
+loaded 1 file(s), type a query or :help
+> Then there's a pair of us
+the end
+> ignore case: on
+> line numbers: on
+> 1:Then there's a pair of us
+> count only: on
+> 3
+> ";
+        assert_eq!(out, expected);
+        // without :quit the session ends with the input, on a line of its own
+        let mut out = Vec::new();
+        repl(
+            &mut config,
+            &documents,
+            &mut ":n\n".as_bytes(),
+            &mut out,
+            &mut stats,
+        )
+        .unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("line numbers: off\n> \n"));
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Text,
+    Json,
+}
+
+impl StatsFormat {
+    pub fn parse(value: &str) -> Result<StatsFormat, String> {
+        match value {
+            "text" => Ok(StatsFormat::Text),
+            "json" => Ok(StatsFormat::Json),
+            other => Err(format!(
+                "invalid stats format '{other}', expected text or json"
+            )),
+        }
+    }
+}
+
+// how much work a search did, filled in as we go and reported with --stats
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Stats {
+    pub files_searched: usize,
+    pub files_matched: usize,
+    pub lines_scanned: usize,
+    pub bytes_read: usize,
+    // every match is counted, so a line with the query twice counts twice
+    pub matches: usize,
+    pub elapsed: Duration,
+}
+
+impl Stats {
+    // the report goes to stderr so stdout can still be piped somewhere else
+    pub fn report(&self, format: StatsFormat, out: &mut impl Write) -> io::Result<()> {
+        match format {
+            StatsFormat::Text => {
+                writeln!(out, "files searched: {}", self.files_searched)?;
+                writeln!(out, "files matched:  {}", self.files_matched)?;
+                writeln!(out, "lines scanned:  {}", self.lines_scanned)?;
+                writeln!(out, "bytes read:     {}", self.bytes_read)?;
+                writeln!(out, "matches found:  {}", self.matches)?;
+                writeln!(out, "elapsed:        {:.3?}", self.elapsed)
+            }
+            StatsFormat::Json => writeln!(
+                out,
+                "{{\"files_searched\":{},\"files_matched\":{},\"lines_scanned\":{},\
+                 \"bytes_read\":{},\"matches\":{},\"elapsed_secs\":{:.6}}}",
+                self.files_searched,
+                self.files_matched,
+                self.lines_scanned,
+                self.bytes_read,
+                self.matches,
+                self.elapsed.as_secs_f64()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+
+    #[test]
+    fn json_report_is_one_line() {
+        let stats = Stats {
+            files_searched: 2,
+            files_matched: 1,
+            lines_scanned: 10,
+            bytes_read: 120,
+            matches: 3,
+            elapsed: Duration::from_millis(1500),
+        };
+        let mut out = Vec::new();
+        stats.report(StatsFormat::Json, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"files_searched\":2,\"files_matched\":1,\"lines_scanned\":10,\
+             \"bytes_read\":120,\"matches\":3,\"elapsed_secs\":1.500000}\n"
+        );
+    }
+}
@@ -1,3 +1,10 @@
+use std::{
+    error::Error,
+    io::{BufRead, Write},
+};
+
+use quiz::Question;
+
 pub mod borrowing;
 pub mod collections;
 pub mod control_flow;
@@ -14,3 +21,106 @@ pub mod structs;
 pub mod tests;
 pub mod variables;
 pub mod mini_grep;
+pub mod script;
+pub mod batch;
+pub mod compile_fail;
+pub mod sources;
+pub mod export;
+pub mod progress;
+pub mod quiz;
+pub mod search;
+pub mod guess;
+pub mod prompt;
+
+#[cfg(test)]
+mod snapshots;
+
+// every example implements this so a front end can find it, describe it and run it
+// the output goes to out instead of straight to stdout, so it can be captured and inspected,
+// and anything the example asks the user for is read from input
+pub trait Example {
+    // the name used on the command line, the same as the module name
+    fn name(&self) -> &'static str;
+    // the chapter of the book the example follows
+    fn chapter(&self) -> u32;
+    fn description(&self) -> &'static str;
+    fn run(&self, out: &mut dyn Write, input: &mut dyn BufRead) -> Result<(), Box<dyn Error>>;
+    // predict-the-output questions about the code of the example
+    fn quiz(&self) -> &'static [Question] {
+        &[]
+    }
+    // the files the example creates in the current directory, an export does not run those
+    fn writes_files(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+// every example, in the order the book introduces them
+pub const REGISTRY: &[&dyn Example] = &[
+    &hello_world::HelloWorld,
+    &guessing_game::GuessingGame,
+    &variables::Variables,
+    &data_types::DataTypes,
+    &functions::Functions,
+    &control_flow::ControlFlow,
+    &ownership::Ownership,
+    &borrowing::Borrowing,
+    &slices::Slices,
+    &structs::Structs,
+    &enums::Enums,
+    &collections::Collections,
+    &errors::Errors,
+    &generics_traits_lifetimes::GenericsTraitsLifetimes,
+    &mini_grep::MiniGrep,
+];
+
+pub fn find(name: &str) -> Option<&'static dyn Example> {
+    REGISTRY
+        .iter()
+        .copied()
+        .find(|example| example.name() == name)
+}
+
+// the titles of the chapters of the book that have examples
+pub fn chapter_title(chapter: u32) -> &'static str {
+    match chapter {
+        1 => "Getting Started",
+        2 => "Programming a Guessing Game",
+        3 => "Common Programming Concepts",
+        4 => "Understanding Ownership",
+        5 => "Using Structs to Structure Related Data",
+        6 => "Enums and Pattern Matching",
+        8 => "Common Collections",
+        9 => "Error Handling",
+        10 => "Generic Types, Traits, and Lifetimes",
+        11 => "Writing Automated Tests",
+        12 => "An I/O Project: Building a Command Line Program",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    #[test]
+    fn names_are_unique_and_chapters_in_order() {
+        let mut names: Vec<&str> = REGISTRY.iter().map(|example| example.name()).collect();
+        let chapters: Vec<u32> = REGISTRY.iter().map(|example| example.chapter()).collect();
+        assert!(chapters.windows(2).all(|pair| pair[0] <= pair[1]));
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), REGISTRY.len());
+    }
+
+    #[test]
+    fn output_can_be_captured() {
+        let mut out = Vec::new();
+        let mut input = "".as_bytes();
+        find("hello_world")
+            .unwrap()
+            .run(&mut out, &mut input)
+            .unwrap();
+        assert_eq!(out, b"hello world\n");
+    }
+}
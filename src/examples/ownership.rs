@@ -1,5 +1,33 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct Ownership;
+
+impl Example for Ownership {
+    fn name(&self) -> &'static str {
+        "ownership"
+    }
+
+    fn chapter(&self) -> u32 {
+        4
+    }
+
+    fn description(&self) -> &'static str {
+        "the ownership rules, moves, clones and passing ownership to functions"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        ownership(out)?;
+        Ok(())
+    }
+}
+
 #[allow(unused)]
-pub fn ownership() {
+pub fn ownership(out: &mut dyn Write) -> io::Result<()> {
     // ownership rules
     // 1. Each value in Rust has an owner.
     // 2. There can only be one owner at a time.
@@ -8,35 +36,38 @@ pub fn ownership() {
     // mutating a string
     let mut s: String = String::from("hello");
     s.push_str(", world");
-    println!("{}", s);
+    writeln!(out, "{}", s)?;
 
     // this code errors because we are trying to access a moved value
     // let s1 = String::from("hello");
     // let s2 = s1;
     // println!("{}, world!", s1);
+    // see `examples explain-error moved-value`
 
     // cloning a value
     let s1 = String::from("hello");
     let s2 = s1.clone();
-    println!("s1 = {}, s2 = {}", s1, s2);
+    writeln!(out, "s1 = {}, s2 = {}", s1, s2)?;
 
     // functions take ownership of parameters
     let s = String::from("hello");
-    fn takes_ownership(some_string: String) {
-        println!("This string is mine: {some_string}");
+    // out is passed along too, so the function can write its output to the same place
+    fn takes_ownership(out: &mut dyn Write, some_string: String) -> io::Result<()> {
+        writeln!(out, "This string is mine: {some_string}")
     }
-    takes_ownership(s);
+    takes_ownership(out, s)?;
     // this will error because s does not exist in this scope any longer
     // println!("{s}");
+    // see `examples explain-error moved-into-function`
 
     // this function returns the original value and places it back into "s"
     let mut s = String::from("hello");
-    fn returns_ownership(some_string: String) -> String {
-        println!("Okay, you can have this string back: {some_string}");
-        some_string
+    fn returns_ownership(out: &mut dyn Write, some_string: String) -> io::Result<String> {
+        writeln!(out, "Okay, you can have this string back: {some_string}")?;
+        Ok(some_string)
     }
-    s = returns_ownership(s);
-    println!("{s}");
+    s = returns_ownership(out, s)?;
+    writeln!(out, "{s}")?;
 
     // retuning multiple values with a tuple
     let mut x = 2;
@@ -47,5 +78,6 @@ pub fn ownership() {
         (num1, num2)
     }
     (x, y) = double_two_numbers(x, y);
-    println!("x = {x} y = {y}");
+    writeln!(out, "x = {x} y = {y}")?;
+    Ok(())
 }
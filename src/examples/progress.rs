@@ -0,0 +1,374 @@
+// which examples a learner has run and which they marked complete, kept in a json file
+// so working through the book can be spread over weeks
+// one file can hold several learners, each under their own name
+// runs at the same time change the file one after the other, under the leaderboard's lock file
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{chapter_title, guessing_game::leaderboard::Lock, Example};
+use crate::json::Json;
+
+pub const PROGRESS_FILE: &str = "progress.json";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExampleProgress {
+    pub runs: u64,
+    // seconds since the unix epoch
+    pub last_run: Option<u64>,
+    pub completed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Learner {
+    pub examples: BTreeMap<String, ExampleProgress>,
+    pub last_visited: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Progress {
+    path: PathBuf,
+    learners: BTreeMap<String, Learner>,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// a file we keep for the user, the variable points at the file itself,
+// otherwise it lives with the other data of the user, like ~/.local/share/rust-book-examples
+pub fn data_path(variable: &str, file: &str) -> Option<PathBuf> {
+    if let Some(path) = env::var_os(variable) {
+        return Some(PathBuf::from(path));
+    }
+    let data = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(data.join("rust-book-examples").join(file))
+}
+
+pub fn progress_path() -> Option<PathBuf> {
+    data_path("RUST_BOOK_PROGRESS", PROGRESS_FILE)
+}
+
+// the name progress is kept under when none is given, the login name if there is one
+pub fn default_learner() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "default".to_string())
+}
+
+fn number(value: Option<&Json>) -> Option<u64> {
+    value.and_then(Json::as_u64)
+}
+
+impl Learner {
+    fn from_json(json: &Json) -> Learner {
+        let mut learner = Learner {
+            last_visited: json
+                .get("last_visited")
+                .and_then(Json::as_str)
+                .map(str::to_string),
+            ..Learner::default()
+        };
+        for (name, example) in json
+            .get("examples")
+            .and_then(Json::as_object)
+            .unwrap_or(&[])
+        {
+            learner.examples.insert(
+                name.clone(),
+                ExampleProgress {
+                    runs: number(example.get("runs")).unwrap_or(0),
+                    last_run: number(example.get("last_run")),
+                    completed: number(example.get("completed")),
+                },
+            );
+        }
+        learner
+    }
+
+    fn to_json(&self) -> Json {
+        let optional = |value: Option<u64>| value.map_or(Json::Null, |n| Json::Number(n as f64));
+        let examples = self
+            .examples
+            .iter()
+            .map(|(name, example)| {
+                let entry = Json::Object(vec![
+                    ("runs".to_string(), Json::Number(example.runs as f64)),
+                    ("last_run".to_string(), optional(example.last_run)),
+                    ("completed".to_string(), optional(example.completed)),
+                ]);
+                (name.clone(), entry)
+            })
+            .collect();
+        Json::Object(vec![
+            (
+                "last_visited".to_string(),
+                self.last_visited.clone().map_or(Json::Null, Json::String),
+            ),
+            ("examples".to_string(), Json::Object(examples)),
+        ])
+    }
+
+    fn is_complete(&self, name: &str) -> bool {
+        self.examples
+            .get(name)
+            .is_some_and(|example| example.completed.is_some())
+    }
+
+    // the first example in book order that is not marked complete
+    pub fn next<'a>(&self, examples: &[&'a dyn Example]) -> Option<&'a dyn Example> {
+        examples
+            .iter()
+            .copied()
+            .find(|example| !self.is_complete(example.name()))
+    }
+}
+
+impl Progress {
+    // a file that does not exist yet is no progress at all
+    pub fn load(path: &Path) -> Result<Progress, String> {
+        let mut progress = Progress {
+            path: path.to_path_buf(),
+            learners: BTreeMap::new(),
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(progress),
+            Err(e) => return Err(format!("can not read {}: {e}", path.display())),
+        };
+        let json = Json::parse(&text).map_err(|e| format!("{} is broken: {e}", path.display()))?;
+        for (name, learner) in json
+            .get("learners")
+            .and_then(Json::as_object)
+            .unwrap_or(&[])
+        {
+            progress
+                .learners
+                .insert(name.clone(), Learner::from_json(learner));
+        }
+        Ok(progress)
+    }
+
+    pub fn to_json(&self) -> Json {
+        let learners = self
+            .learners
+            .iter()
+            .map(|(name, learner)| (name.clone(), learner.to_json()))
+            .collect();
+        Json::Object(vec![
+            ("version".to_string(), Json::Number(1.0)),
+            ("learners".to_string(), Json::Object(learners)),
+        ])
+    }
+
+    // the change is made to what is in the file right now, not to what was there when the run began,
+    // and hands back what the change did
+    pub fn update<T>(path: &Path, change: impl FnOnce(&mut Progress) -> T) -> Result<T, String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("can not create {}: {e}", dir.display()))?;
+        }
+        let _lock =
+            Lock::acquire(path).map_err(|e| format!("can not lock {}: {e}", path.display()))?;
+        let mut progress = Progress::load(path)?;
+        let changed = change(&mut progress);
+        progress.save()?;
+        Ok(changed)
+    }
+
+    // written next to the old file first and renamed over it, so a crash never leaves half a file
+    // every process writes to a file of its own, in case a stale lock was taken over
+    fn save(&self) -> Result<(), String> {
+        let tmp = self
+            .path
+            .with_extension(format!("json.{}.tmp", process::id()));
+        fs::write(&tmp, self.to_json().pretty() + "\n")
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .map_err(|e| format!("can not write {}: {e}", self.path.display()))
+    }
+
+    pub fn learner(&self, name: &str) -> Learner {
+        self.learners.get(name).cloned().unwrap_or_default()
+    }
+
+    pub fn record_run(&mut self, learner: &str, example: &str, at: u64) {
+        let learner = self.learners.entry(learner.to_string()).or_default();
+        let entry = learner.examples.entry(example.to_string()).or_default();
+        entry.runs += 1;
+        entry.last_run = Some(at);
+        learner.last_visited = Some(example.to_string());
+    }
+
+    pub fn mark_complete(&mut self, learner: &str, example: &str, at: u64) {
+        let learner = self.learners.entry(learner.to_string()).or_default();
+        learner
+            .examples
+            .entry(example.to_string())
+            .or_default()
+            .completed
+            .get_or_insert(at);
+        learner.last_visited = Some(example.to_string());
+    }
+
+    // gives back whether there was anything to forget
+    pub fn reset(&mut self, learner: &str) -> bool {
+        self.learners.remove(learner).is_some()
+    }
+}
+
+fn ago(then: u64, now: u64) -> String {
+    let secs = now.saturating_sub(then);
+    let (amount, unit) = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => (secs / 60, "minute"),
+        3600..=86_399 => (secs / 3600, "hour"),
+        _ => (secs / 86_400, "day"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    format!("{amount} {unit}{plural} ago")
+}
+
+// completion per chapter, each example with [x] for complete, [~] for run and [ ] for not yet
+pub fn show(
+    learner_name: &str,
+    learner: &Learner,
+    examples: &[&dyn Example],
+    now: u64,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writeln!(out, "progress of {learner_name}")?;
+    let mut chapters: Vec<u32> = examples.iter().map(|example| example.chapter()).collect();
+    chapters.dedup();
+    for chapter in chapters {
+        let in_chapter: Vec<&&dyn Example> = examples
+            .iter()
+            .filter(|example| example.chapter() == chapter)
+            .collect();
+        let done = in_chapter
+            .iter()
+            .filter(|example| learner.is_complete(example.name()))
+            .count();
+        writeln!(
+            out,
+            "\nchapter {chapter}: {} ({done}/{} complete)",
+            chapter_title(chapter),
+            in_chapter.len()
+        )?;
+        for example in in_chapter {
+            let (mark, note) = match learner.examples.get(example.name()) {
+                Some(p) if p.completed.is_some() => ("x", String::new()),
+                Some(p) => match p.last_run {
+                    Some(last) => ("~", format!(", last run {}", ago(last, now))),
+                    None => ("~", String::new()),
+                },
+                None => (" ", String::new()),
+            };
+            let runs = learner.examples.get(example.name()).map_or(0, |p| p.runs);
+            let runs = match runs {
+                0 => String::new(),
+                1 => format!("run once{note}"),
+                n => format!("run {n} times{note}"),
+            };
+            let line = format!("  [{mark}] {:<27} {runs}", example.name());
+            writeln!(out, "{}", line.trim_end())?;
+        }
+    }
+
+    let complete = examples
+        .iter()
+        .filter(|example| learner.is_complete(example.name()))
+        .count();
+    writeln!(out, "\n{complete} of {} examples complete", examples.len())?;
+    if let Some(last) = &learner.last_visited {
+        writeln!(out, "last visited: {last}")?;
+    }
+    match learner.next(examples) {
+        Some(next) => writeln!(
+            out,
+            "next up: {} ({}), mark it done with `examples progress complete {}`",
+            next.name(),
+            next.description(),
+            next.name()
+        ),
+        None => writeln!(out, "every example is complete, well done!"),
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use super::*;
+    use crate::examples::REGISTRY;
+
+    #[test]
+    fn runs_and_completions_survive_a_save() {
+        let dir = env::temp_dir().join(format!("progress-test-{}", std::process::id()));
+        let path = dir.join(PROGRESS_FILE);
+        Progress::update(&path, |progress| {
+            progress.record_run("ada", "hello_world", 100);
+            progress.record_run("ada", "hello_world", 200);
+            progress.mark_complete("ada", "hello_world", 300);
+            progress.record_run("ada", "variables", 400);
+            progress.record_run("bob", "ownership", 500);
+        })
+        .unwrap();
+
+        let mut progress = Progress::load(&path).unwrap();
+        let ada = progress.learner("ada");
+        assert_eq!(
+            ada.examples["hello_world"],
+            ExampleProgress {
+                runs: 2,
+                last_run: Some(200),
+                completed: Some(300)
+            }
+        );
+        assert_eq!(ada.last_visited.as_deref(), Some("variables"));
+        assert_eq!(ada.next(REGISTRY).unwrap().name(), "guessing_game");
+
+        let mut out = Vec::new();
+        show("ada", &ada, REGISTRY, 4000, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("chapter 1: Getting Started (1/1 complete)"));
+        assert!(out.contains("[~] variables                   run once, last run 1 hour ago"));
+
+        assert!(progress.reset("bob"));
+        assert_eq!(progress.learner("bob"), Learner::default());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn runs_at_the_same_time_keep_each_others_progress() {
+        let dir = env::temp_dir().join(format!("progress-race-{}", std::process::id()));
+        let path = dir.join(PROGRESS_FILE);
+        let runs: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    Progress::update(&path, |progress| {
+                        progress.record_run("ada", "hello_world", i)
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for run in runs {
+            run.join().unwrap();
+        }
+        let progress = Progress::load(&path).unwrap();
+        assert_eq!(progress.learner("ada").examples["hello_world"].runs, 8);
+        assert!(!path.with_extension("json.lock").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,272 @@
+// asking the user for something typed in, the read_line, trim and parse from the book in one place
+// an answer that does not parse or does not pass a check is turned down with the reason,
+// and the question is asked again, as often as tries allows
+// the end of the input is an error of its own, so a game can end cleanly on it
+
+use std::{
+    any::type_name,
+    error::Error,
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use super::guess::Guess;
+
+// turns the answer into a T, or says why it can not
+type Parse<'a, T> = Box<dyn Fn(&str) -> Result<T, String> + 'a>;
+type Check<'a, T> = Box<dyn Fn(&T) -> Result<(), String> + 'a>;
+
+pub struct Prompt<'a, T> {
+    question: &'a str,
+    parse: Parse<'a, T>,
+    checks: Vec<Check<'a, T>>,
+    // the value for an empty answer, with how it is shown after the question
+    default: Option<(T, String)>,
+    // None keeps asking until the input runs out
+    tries: Option<u32>,
+    // the answer is typed on the line of the question, like a shell prompt
+    inline: bool,
+    // the answer keeps the spaces around it, only the newline is taken off
+    untrimmed: bool,
+}
+
+#[derive(Debug)]
+pub enum PromptError {
+    // the input ended, with the reason the last answer was turned down if there was one
+    Closed { rejected: Option<String> },
+    TooManyTries { tries: u32, reason: String },
+    Io(io::Error),
+}
+
+// the question is asked for a T parsed with its FromStr, "42" for a u32
+pub fn prompt<'a, T: FromStr + 'a>(question: &'a str) -> Prompt<'a, T> {
+    Prompt::parse_with(question, |text| {
+        if text.is_empty() {
+            return Err("nothing was typed".to_string());
+        }
+        let name = type_name::<T>().rsplit("::").next().unwrap_or("value");
+        text.parse()
+            .map_err(|_| format!("'{text}' is not a valid {name}"))
+    })
+}
+
+// an index into items, so using it can not panic
+pub fn index_prompt<'a, T>(question: &'a str, items: &[T]) -> Prompt<'a, usize> {
+    let len = items.len();
+    Prompt::parse_with(question, move |text| {
+        if len == 0 {
+            return Err("there is nothing to pick from".to_string());
+        }
+        Guess::parse(text, 0..=len - 1)
+            .map(Guess::value)
+            .map_err(|e| e.to_string())
+    })
+}
+
+impl<'a, T> Prompt<'a, T> {
+    // for answers FromStr does not cover, like a command or a number
+    pub fn parse_with(
+        question: &'a str,
+        parse: impl Fn(&str) -> Result<T, String> + 'a,
+    ) -> Prompt<'a, T> {
+        Prompt {
+            question,
+            parse: Box::new(parse),
+            checks: Vec::new(),
+            default: None,
+            tries: None,
+            inline: false,
+            untrimmed: false,
+        }
+    }
+
+    // checks run in the order they were added, the first one to fail gives the reason
+    pub fn check(mut self, check: impl Fn(&T) -> Result<(), String> + 'a) -> Prompt<'a, T> {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    pub fn default_value(mut self, value: T) -> Prompt<'a, T>
+    where
+        T: fmt::Display,
+    {
+        let shown = value.to_string();
+        self.default = Some((value, shown));
+        self
+    }
+
+    pub fn tries(mut self, tries: u32) -> Prompt<'a, T> {
+        self.tries = Some(tries.max(1));
+        self
+    }
+
+    pub fn inline(mut self) -> Prompt<'a, T> {
+        self.inline = true;
+        self
+    }
+
+    pub fn untrimmed(mut self) -> Prompt<'a, T> {
+        self.untrimmed = true;
+        self
+    }
+
+    fn answer(&self, text: &str) -> Result<T, String>
+    where
+        T: Clone,
+    {
+        if let (Some((default, _)), "") = (&self.default, text) {
+            return Ok(default.clone());
+        }
+        let value = (self.parse)(text)?;
+        for check in &self.checks {
+            check(&value)?;
+        }
+        Ok(value)
+    }
+
+    pub fn ask(&self, out: &mut dyn Write, input: &mut dyn BufRead) -> Result<T, PromptError>
+    where
+        T: Clone,
+    {
+        let mut rejected = None;
+        let mut tries = 0;
+        loop {
+            match &self.default {
+                Some((_, shown)) => write!(out, "{} [{shown}]", self.question)?,
+                None => write!(out, "{}", self.question)?,
+            }
+            if self.inline {
+                write!(out, " ")?;
+            } else {
+                writeln!(out)?;
+            }
+            out.flush()?;
+
+            let mut line = String::new();
+            // the default is for an empty answer, a closed input is no answer at all
+            if input.read_line(&mut line)? == 0 {
+                return Err(PromptError::Closed { rejected });
+            }
+
+            tries += 1;
+            let text = if self.untrimmed {
+                let line = line.strip_suffix('\n').unwrap_or(&line);
+                line.strip_suffix('\r').unwrap_or(line)
+            } else {
+                line.trim()
+            };
+            match self.answer(text) {
+                Ok(value) => return Ok(value),
+                Err(reason) => {
+                    writeln!(out, "{reason}.")?;
+                    if self.tries.is_some_and(|max| tries >= max) {
+                        return Err(PromptError::TooManyTries { tries, reason });
+                    }
+                    rejected = Some(reason);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromptError::Closed { rejected: None } => {
+                write!(f, "the input ended before an answer was given")
+            }
+            PromptError::Closed {
+                rejected: Some(reason),
+            } => write!(
+                f,
+                "the input ended, the last answer was turned down: {reason}"
+            ),
+            PromptError::TooManyTries { tries, reason } => {
+                write!(
+                    f,
+                    "gave up after {tries} tries, the last one because {reason}"
+                )
+            }
+            PromptError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for PromptError {}
+
+impl From<io::Error> for PromptError {
+    fn from(e: io::Error) -> PromptError {
+        PromptError::Io(e)
+    }
+}
+
+// so a prompt can be asked with ? in the examples, which return io::Result
+impl From<PromptError> for io::Error {
+    fn from(e: PromptError) -> io::Error {
+        match e {
+            PromptError::Io(e) => e,
+            PromptError::Closed { .. } => io::Error::new(io::ErrorKind::UnexpectedEof, e),
+            PromptError::TooManyTries { .. } => io::Error::new(io::ErrorKind::InvalidInput, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod prompt_tests {
+    use super::*;
+
+    fn ask<T: Clone>(prompt: &Prompt<T>, input: &str) -> (Result<T, PromptError>, String) {
+        let mut out = Vec::new();
+        let answer = prompt.ask(&mut out, &mut input.as_bytes());
+        (answer, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn asks_again_until_the_answer_is_good() {
+        let even = prompt::<i32>("An even number?")
+            .check(|n| match n % 2 {
+                0 => Ok(()),
+                _ => Err(format!("{n} is odd")),
+            })
+            .tries(3);
+        let (answer, out) = ask(&even, "seven\n7\n8\n");
+        assert_eq!(answer.unwrap(), 8);
+        assert_eq!(
+            out,
+            "An even number?\n'seven' is not a valid i32.\nAn even number?\n7 is odd.\nAn even number?\n"
+        );
+
+        let (answer, _) = ask(&even, "1\n3\n5\n8\n");
+        assert_eq!(
+            answer.unwrap_err().to_string(),
+            "gave up after 3 tries, the last one because 5 is odd"
+        );
+    }
+
+    #[test]
+    fn defaults_and_the_end_of_the_input() {
+        let name = prompt::<String>("Your name?").default_value("ferris".to_string());
+        let (answer, out) = ask(&name, "\n");
+        assert_eq!(answer.unwrap(), "ferris");
+        assert_eq!(out, "Your name? [ferris]\n");
+        assert!(matches!(
+            ask(&name, "").0,
+            Err(PromptError::Closed { rejected: None })
+        ));
+
+        let arr = [3; 5];
+        let index = index_prompt("Please enter an array index.", &arr);
+        assert_eq!(ask(&index, "4\n").0.unwrap(), 4);
+        let (answer, out) = ask(&index, "5\n");
+        assert!(out.contains("5 is above the range, it has to be from 0 to 4.\n"));
+        assert!(matches!(
+            answer,
+            Err(PromptError::Closed { rejected: Some(_) })
+        ));
+        assert!(matches!(
+            ask(&index, "").0,
+            Err(PromptError::Closed { rejected: None })
+        ));
+    }
+}
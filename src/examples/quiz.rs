@@ -0,0 +1,173 @@
+// predict the output: a snippet from an example is shown, the learner types what they think
+// it prints, and then the code is run for real and the two are compared
+// the questions live in the example modules themselves, each one next to the code it is about
+
+use std::io::{self, BufRead, Write};
+
+use super::Example;
+
+pub struct Question {
+    // shown to the learner, written the way the book would, with println!
+    pub code: &'static str,
+    // runs the same code, writing to out where the snippet prints,
+    // a test compiles code with rustc and checks that the two print the same
+    pub run: fn(&mut dyn Write) -> io::Result<()>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Score {
+    pub correct: usize,
+    pub asked: usize,
+}
+
+// answers are compared line by line, ignoring the spaces around them and empty lines at the end
+fn normalize(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+// the lines typed until an empty one, or None once the input has run out
+fn read_answer(input: &mut dyn BufRead) -> io::Result<Option<String>> {
+    let mut answer = String::new();
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok((!answer.is_empty()).then_some(answer));
+        }
+        if line.trim().is_empty() {
+            return Ok(Some(answer));
+        }
+        answer += &line;
+    }
+}
+
+// asks every question of the examples in turn, until they are done or the input runs out
+pub fn quiz(
+    examples: &[&dyn Example],
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+) -> io::Result<Score> {
+    let questions: Vec<(&str, &Question)> = examples
+        .iter()
+        .flat_map(|example| example.quiz().iter().map(|q| (example.name(), q)))
+        .collect();
+    let mut score = Score::default();
+    for (number, (name, question)) in questions.iter().enumerate() {
+        writeln!(
+            out,
+            "question {} of {} (from {name}), what does this print?\n",
+            number + 1,
+            questions.len()
+        )?;
+        for line in question.code.trim().lines() {
+            writeln!(out, "    {line}")?;
+        }
+        writeln!(out, "\ntype the output, then an empty line:")?;
+        out.flush()?;
+        let Some(answer) = read_answer(input)? else {
+            writeln!(out, "\nout of answers, stopping here")?;
+            break;
+        };
+
+        let mut printed = Vec::new();
+        (question.run)(&mut printed)?;
+        let printed = String::from_utf8_lossy(&printed);
+        score.asked += 1;
+        if normalize(&answer) == normalize(&printed) {
+            score.correct += 1;
+            writeln!(out, "correct!\n")?;
+        } else {
+            writeln!(out, "not quite, it printed:\n")?;
+            for line in printed.lines() {
+                writeln!(out, "    {line}")?;
+            }
+            writeln!(out)?;
+        }
+    }
+    writeln!(
+        out,
+        "score: {} of {} correct ({} questions in total)",
+        score.correct,
+        score.asked,
+        questions.len()
+    )?;
+    Ok(score)
+}
+
+#[cfg(test)]
+mod quiz_tests {
+    use super::*;
+    use crate::examples::{data_types::DataTypes, REGISTRY};
+    use std::{env, fs, process::Command};
+
+    #[test]
+    fn answers_are_checked_against_the_real_output() {
+        let mut out = Vec::new();
+        let mut input = "-1\n\nwrong\n\n".as_bytes();
+        let score = quiz(&[&DataTypes], &mut out, &mut input).unwrap();
+        assert_eq!(
+            score,
+            Score {
+                correct: 1,
+                asked: 2
+            }
+        );
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("let truncated = -5 / 3;"));
+        assert!(out.contains("not quite, it printed:\n\n    3\n"));
+    }
+
+    #[test]
+    fn every_question_runs() {
+        for example in REGISTRY {
+            for question in example.quiz() {
+                let mut out = Vec::new();
+                (question.run)(&mut out).unwrap();
+                assert!(
+                    !out.is_empty(),
+                    "a question of {} prints nothing",
+                    example.name()
+                );
+            }
+        }
+    }
+
+    // the code the learner sees is compiled and run for real, and it has to print
+    // exactly what run prints, so the two copies can not drift apart
+    #[test]
+    fn every_question_prints_what_its_code_prints() {
+        let dir = env::temp_dir().join(format!("rust-book-examples-quiz-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        for example in REGISTRY {
+            for (number, question) in example.quiz().iter().enumerate() {
+                let name = format!("{}_{}", example.name(), number + 1);
+                let file = format!("{name}.rs");
+                let source = format!("fn main() {{\n{}}}\n", question.code);
+                fs::write(dir.join(&file), source).unwrap();
+                let compiled = Command::new(&rustc)
+                    .args(["--edition", "2021", "-A", "warnings", "-o", &name, &file])
+                    .current_dir(&dir)
+                    .output()
+                    .unwrap();
+                assert!(
+                    compiled.status.success(),
+                    "the code of {name} does not compile:\n{}",
+                    String::from_utf8_lossy(&compiled.stderr)
+                );
+                let printed = Command::new(dir.join(&name)).output().unwrap().stdout;
+                let mut out = Vec::new();
+                (question.run)(&mut out).unwrap();
+                assert_eq!(
+                    String::from_utf8_lossy(&out),
+                    String::from_utf8_lossy(&printed),
+                    "run of {name} does not print what its code does"
+                );
+            }
+        }
+        fs::remove_dir_all(&dir).ok();
+    }
+}
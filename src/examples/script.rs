@@ -0,0 +1,90 @@
+// scripted answers for the examples that read from stdin, so they can run without anyone typing
+// every answer is handed out as one line, and once they are used up the script ends
+// like a closed stdin does, the prompt that was asking then says an answer was missing
+// the script remembers it was read past its end, so the runner can stop with an error
+
+use std::io::{self, BufRead, Read};
+
+#[derive(Debug, Clone, Default)]
+pub struct Script {
+    text: Vec<u8>,
+    pos: usize,
+    ran_out: bool,
+}
+
+impl Script {
+    pub fn new() -> Script {
+        Script::default()
+    }
+
+    // one answer per line, so "3\n4" is two answers
+    pub fn push(&mut self, answers: &str) {
+        for answer in answers.lines() {
+            self.text.extend_from_slice(answer.as_bytes());
+            self.text.push(b'\n');
+        }
+    }
+
+    // the same answers from the start, so every example in a batch sees all of them
+    pub fn rewind(&mut self) {
+        self.pos = 0;
+        self.ran_out = false;
+    }
+
+    // whether something asked for another answer after the last one was used
+    pub fn ran_out(&self) -> bool {
+        self.ran_out
+    }
+}
+
+impl Read for Script {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Script {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.text.len() {
+            self.ran_out = true;
+        }
+        Ok(&self.text[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.text.len());
+    }
+}
+
+#[cfg(test)]
+mod script_tests {
+    use super::*;
+
+    #[test]
+    fn answers_come_out_one_line_at_a_time() {
+        let mut script = Script::new();
+        script.push("5\n7");
+        script.push("9");
+        let mut line = String::new();
+        for expected in ["5\n", "7\n", "9\n"] {
+            line.clear();
+            script.read_line(&mut line).unwrap();
+            assert_eq!(line, expected);
+        }
+        assert!(!script.ran_out());
+        line.clear();
+        assert_eq!(script.read_line(&mut line).unwrap(), 0);
+        assert_eq!(line, "");
+        assert!(script.ran_out());
+
+        script.rewind();
+        assert!(!script.ran_out());
+        line.clear();
+        script.read_line(&mut line).unwrap();
+        assert_eq!(line, "5\n");
+    }
+}
@@ -0,0 +1,129 @@
+// finds where a topic is shown, by searching the source of every example with mini_grep
+// each hit comes with the comment that introduces the code it is in, which is how the lessons are split up
+
+use std::io::{self, Write};
+
+use super::{
+    mini_grep::search,
+    sources::{prose_comment, SourceFile, SOURCES},
+};
+
+pub struct Hit {
+    pub example: &'static str,
+    pub path: &'static str,
+    pub line_number: usize,
+    pub line: &'static str,
+    pub heading: Option<&'static str>,
+}
+
+fn is_comment(line: &str) -> bool {
+    prose_comment(line).is_some_and(|comment| !comment.is_empty())
+}
+
+// the first line of the closest comment block at or above the line at index,
+// "// using else in if let" for the code that shows it
+// the search stops at the function the line is in, only the comment right above that function
+// is still about it, and at the } that ends a top level item, the comment above it is about other code
+// a } further in only closes a block, like the else of an if let, and the comment above still holds
+fn heading(lines: &[&'static str], index: usize) -> Option<&'static str> {
+    let mut at = index;
+    while !is_comment(lines[at]) {
+        let code = lines[at].trim_start();
+        let starts_fn = code.starts_with("fn ") || code.starts_with("pub fn ");
+        let ends_item = lines[at].starts_with('}');
+        if at == 0 || (at < index && (ends_item || starts_fn && !is_comment(lines[at - 1]))) {
+            return None;
+        }
+        at -= 1;
+    }
+    while at > 0 && is_comment(lines[at - 1]) {
+        at -= 1;
+    }
+    prose_comment(lines[at])
+}
+
+fn search_file(query: &str, file: &'static SourceFile) -> Vec<Hit> {
+    let lines: Vec<&'static str> = file.text.lines().collect();
+    // one line at a time, so the index of the line is the line number
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !search(query, line).is_empty())
+        .map(|(index, &line)| Hit {
+            example: file.example,
+            path: file.path,
+            line_number: index + 1,
+            line,
+            heading: heading(&lines, index),
+        })
+        .collect()
+}
+
+// only the files with the lessons, not the tools built around the examples
+pub fn search_sources(query: &str) -> Vec<Hit> {
+    SOURCES
+        .iter()
+        .filter(|file| file.lesson)
+        .flat_map(|file| search_file(query, file))
+        .collect()
+}
+
+pub fn print_hits(hits: &[Hit], out: &mut dyn Write) -> io::Result<()> {
+    for hit in hits {
+        // a hit on the heading itself does not need it twice
+        let heading = match hit.heading {
+            Some(heading) if Some(heading) != prose_comment(hit.line) => format!(" // {heading}"),
+            _ => String::new(),
+        };
+        writeln!(
+            out,
+            "{} (src/examples/{}:{}){heading}",
+            hit.example, hit.path, hit.line_number
+        )?;
+        writeln!(out, "    {}", hit.line.trim())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod search_tests {
+    use super::*;
+
+    #[test]
+    fn hits_know_their_line_and_heading() {
+        let hits = search_sources("if let Coin::Quarter");
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert_eq!(hit.example, "enums");
+        assert_eq!(hit.heading, Some("using else in if let"));
+        let source = SOURCES.iter().find(|file| file.path == "enums.rs").unwrap();
+        assert_eq!(source.text.lines().nth(hit.line_number - 1), Some(hit.line));
+
+        let hits = search_sources("'counting_up: loop");
+        assert_eq!(hits[0].heading, Some("loops within loops"));
+
+        // a } that only closes a block does not hide the comment above it
+        let hits = search_sources("_count += 1");
+        assert_eq!(hits[0].heading, Some("using else in if let"));
+
+        // the tools around the examples are not searched
+        assert!(search_sources("fn broadcast").is_empty());
+    }
+
+    #[test]
+    fn a_heading_is_not_taken_from_another_function() {
+        let lines = [
+            "// prints the greeting",
+            "fn greet() {",
+            "    println!(\"hi\");",
+            "}",
+            "",
+            "fn other() {",
+            "    let x = 1;",
+            "}",
+        ];
+        assert_eq!(heading(&lines, 2), Some("prints the greeting"));
+        assert_eq!(heading(&lines, 6), None);
+        assert_eq!(heading(&lines, 1), Some("prints the greeting"));
+    }
+}
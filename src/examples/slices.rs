@@ -1,11 +1,39 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct Slices;
+
+impl Example for Slices {
+    fn name(&self) -> &'static str {
+        "slices"
+    }
+
+    fn chapter(&self) -> u32 {
+        4
+    }
+
+    fn description(&self) -> &'static str {
+        "string slices and array slices"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        slices(out)?;
+        Ok(())
+    }
+}
+
 #[allow(unused)]
-pub fn slices() {
+pub fn slices(out: &mut dyn Write) -> io::Result<()> {
     // refering to part of a string
     let s = String::from("hello world");
     let hello = &s[..5];
     let world = &s[6..11];
     let whole = &s[..];
-    println!("slice1 = {hello} slice2 = {world} slice3 = {whole}");
+    writeln!(out, "slice1 = {hello} slice2 = {world} slice3 = {whole}")?;
 
     // getting the first word of a string
     // we make the parameter s of type &str so we can use both strings and &str types
@@ -23,7 +51,7 @@ pub fn slices() {
     }
     let s = String::from("This is many words!");
     let first = first_word(&s);
-    println!("the first word is: {first}");
+    writeln!(out, "the first word is: {first}")?;
 
     // slices are tied to the original string they are derived from
     // if the original string goes out of scope, the slice becomes invalid
@@ -34,4 +62,5 @@ pub fn slices() {
     let a = [1, 2, 3, 4, 5];
     let slice = &a[1..3];
     assert_eq!(slice, &[2, 3]); // setting a test, if the values are not equal, an error will be thrown
+    Ok(())
 }
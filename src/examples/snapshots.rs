@@ -0,0 +1,209 @@
+// golden snapshot tests: every example is run with fixed input and its output is compared
+// to the .snap file with the same name in src/examples/snapshots
+// after an intended change to an example, regenerate the files with
+// UPDATE_SNAPSHOTS=1 cargo test snapshots
+// and review the new .snap files in the diff before committing them
+
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{BufRead, Write},
+    path::PathBuf,
+};
+
+use super::{
+    guessing_game::{guessing_game, Config},
+    Example, REGISTRY,
+};
+
+// examples that print hash maps, whose order changes from run to run,
+// the entries are put in order before the output is compared
+const UNORDERED: &[&str] = &["collections"];
+
+// the guessing game is played against a fixed seed, so the secret is always 5
+const SEED: &str = "7";
+
+fn run(
+    example: &dyn Example,
+    out: &mut dyn Write,
+    input: &mut dyn BufRead,
+) -> Result<(), Box<dyn Error>> {
+    match example.name() {
+        "guessing_game" => {
+            guessing_game(out, input, &Config::build(&["--seed", SEED])?)?;
+            Ok(())
+        }
+        _ => example.run(out, input),
+    }
+}
+
+// "{"b": 2, "a": 1}" becomes "{"a": 1, "b": 2}", and lines like "Blue: 10" that follow
+// each other, printed by a loop over a map, are sorted among themselves
+fn sort_map_entries(output: &str) -> String {
+    let is_entry = |line: &str| {
+        line.split_once(": ")
+            .is_some_and(|(key, value)| !key.contains(' ') && value.parse::<i64>().is_ok())
+    };
+    let mut lines: Vec<String> = Vec::new();
+    let mut entries: Vec<&str> = Vec::new();
+    for line in output.lines() {
+        if is_entry(line) {
+            entries.push(line);
+            continue;
+        }
+        entries.sort();
+        lines.extend(entries.drain(..).map(str::to_string));
+        match line
+            .strip_prefix('{')
+            .and_then(|line| line.strip_suffix('}'))
+        {
+            Some(inner) => {
+                let mut inner: Vec<&str> = inner.split(", ").collect();
+                inner.sort();
+                lines.push(format!("{{{}}}", inner.join(", ")));
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    entries.sort();
+    lines.extend(entries.drain(..).map(str::to_string));
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+// what gets typed into the examples that read from stdin
+fn input_for(name: &str) -> &'static str {
+    match name {
+        "data_types" => "2\n",
+        // a bit of everything the game takes, then closing in on the secret
+        "guessing_game" => "hint\nhistory\nfifty\n50\n8\n3\nhistory\n5\n",
+        _ => "",
+    }
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/examples/snapshots")
+        .join(format!("{name}.snap"))
+}
+
+// a line diff of the part of the two texts that differs, with a little context around it
+// the common start and end are skipped first so the table in the middle stays small
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &expected[prefix..expected.len() - suffix];
+    let new = &actual[prefix..actual.len() - suffix];
+
+    // lengths of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    const CONTEXT: usize = 2;
+    let mut report = format!("@@ line {} @@\n", prefix + 1);
+    for line in &expected[prefix.saturating_sub(CONTEXT)..prefix] {
+        report += &format!("  {line}\n");
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            report += &format!("  {}\n", old[i]);
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] > lcs[i + 1][j]) {
+            report += &format!("+ {}\n", new[j]);
+            j += 1;
+        } else {
+            report += &format!("- {}\n", old[i]);
+            i += 1;
+        }
+    }
+    let after = expected.len() - suffix;
+    for line in expected[after..].iter().take(CONTEXT) {
+        report += &format!("  {line}\n");
+    }
+    report
+}
+
+#[test]
+fn every_example_matches_its_snapshot() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut failures = Vec::new();
+    for example in REGISTRY {
+        let name = example.name();
+        let mut out = Vec::new();
+        let mut input = input_for(name).as_bytes();
+        if let Err(e) = run(*example, &mut out, &mut input) {
+            failures.push(format!("{name} failed to run: {e}"));
+            continue;
+        }
+        let mut actual = String::from_utf8(out).expect("examples write utf-8");
+        if UNORDERED.contains(&name) {
+            actual = sort_map_entries(&actual);
+        }
+        let path = snapshot_path(name);
+        if update {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{name} does not match {}\n--- expected\n+++ actual\n{}",
+                path.display(),
+                diff(&expected, &actual)
+            )),
+            Err(e) => failures.push(format!(
+                "{name} has no snapshot at {} ({e}), run with UPDATE_SNAPSHOTS=1 to create it",
+                path.display()
+            )),
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn unordered_examples_exist() {
+    for name in UNORDERED {
+        assert!(REGISTRY.iter().any(|example| example.name() == *name));
+    }
+}
+
+#[test]
+fn map_entries_are_sorted() {
+    let output = "scores\nYellow: 50\nBlue: 10\n{\"b\": 2, \"a\": 1}\n";
+    assert_eq!(
+        sort_map_entries(output),
+        "scores\nBlue: 10\nYellow: 50\n{\"a\": 1, \"b\": 2}\n"
+    );
+}
+
+#[test]
+fn diff_shows_changed_lines_with_context() {
+    let expected = "one\ntwo\nthree\nfour\nfive\nsix\n";
+    let actual = "one\ntwo\nthree\n4\nfive\nsix\nseven\n";
+    assert_eq!(
+        diff(expected, actual),
+        "@@ line 4 @@\n  two\n  three\n- four\n+ 4\n  five\n  six\n+ seven\n"
+    );
+}
@@ -0,0 +1,114 @@
+// the source code of every example, embedded when the crate is built
+// so it can be shown and searched without the repository around
+
+pub struct SourceFile {
+    // the name of the example the file belongs to
+    pub example: &'static str,
+    // relative to src/examples
+    pub path: &'static str,
+    pub text: &'static str,
+    // false for the tools built around an example, like the game server or the word index,
+    // which are not what the book teaches, so searching the examples leaves them out
+    pub lesson: bool,
+}
+
+macro_rules! source {
+    ($example:literal, $path:literal) => {
+        SourceFile {
+            example: $example,
+            path: $path,
+            text: include_str!($path),
+            lesson: true,
+        }
+    };
+    ($example:literal, $path:literal, tool) => {
+        SourceFile {
+            lesson: false,
+            ..source!($example, $path)
+        }
+    };
+}
+
+// in the same order as the registry, an example made of several files lists its main file first
+pub const SOURCES: &[SourceFile] = &[
+    source!("hello_world", "hello_world.rs"),
+    source!("guessing_game", "guessing_game/mod.rs"),
+    source!("guessing_game", "guessing_game/game.rs"),
+    source!("guessing_game", "guessing_game/leaderboard.rs", tool),
+    source!("guessing_game", "guessing_game/recording.rs", tool),
+    source!("guessing_game", "guessing_game/server.rs", tool),
+    source!("guessing_game", "guessing_game/solver.rs", tool),
+    source!("guessing_game", "guessing_game/words.rs", tool),
+    source!("variables", "variables.rs"),
+    source!("data_types", "data_types.rs"),
+    source!("functions", "functions.rs"),
+    source!("control_flow", "control_flow.rs"),
+    source!("ownership", "ownership.rs"),
+    source!("borrowing", "borrowing.rs"),
+    source!("slices", "slices.rs"),
+    source!("structs", "structs.rs"),
+    source!("enums", "enums.rs"),
+    source!("collections", "collections.rs"),
+    source!("errors", "errors.rs"),
+    source!("generics_traits_lifetimes", "generics_traits_lifetimes.rs"),
+    source!("mini_grep", "mini_grep/mod.rs"),
+    source!("mini_grep", "mini_grep/config.rs", tool),
+    source!("mini_grep", "mini_grep/format.rs", tool),
+    source!("mini_grep", "mini_grep/index.rs", tool),
+    source!("mini_grep", "mini_grep/repl.rs", tool),
+    source!("mini_grep", "mini_grep/stats.rs", tool),
+];
+
+pub fn files_of(example: &str) -> impl Iterator<Item = &'static SourceFile> + '_ {
+    SOURCES.iter().filter(move |file| file.example == example)
+}
+
+// the text of a whole line comment, None for a line of code
+// a comment that ends like a line of code is code that was commented out, so it counts as code too
+pub fn prose_comment(line: &str) -> Option<&str> {
+    let comment = line.trim_start().strip_prefix("//")?;
+    if comment.starts_with('/') {
+        return None;
+    }
+    let comment = comment.trim();
+    (!comment.ends_with([';', '{', '}', ','])).then_some(comment)
+}
+
+#[cfg(test)]
+mod sources_tests {
+    use super::*;
+    use crate::examples::REGISTRY;
+
+    #[test]
+    fn every_example_has_its_source() {
+        for example in REGISTRY {
+            let file = files_of(example.name()).next().unwrap();
+            assert!(file.text.contains(&format!("\"{}\"", example.name())));
+        }
+    }
+
+    // the list is kept by hand, so a file added to an example has to be added there too
+    #[test]
+    fn every_file_of_every_example_is_listed() {
+        let examples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/examples");
+        for example in REGISTRY {
+            let name = example.name();
+            let main = files_of(name).next().unwrap().path;
+            if main == format!("{name}.rs") {
+                continue;
+            }
+            assert_eq!(main, format!("{name}/mod.rs"));
+            for entry in std::fs::read_dir(examples.join(name)).unwrap() {
+                let file = entry.unwrap().file_name().to_string_lossy().into_owned();
+                if !file.ends_with(".rs") {
+                    continue;
+                }
+                let path = format!("{name}/{file}");
+                assert!(
+                    files_of(name).any(|source| source.path == path),
+                    "{path} is missing from SOURCES"
+                );
+            }
+        }
+    }
+}
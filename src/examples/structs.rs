@@ -1,5 +1,33 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::Example;
+
+pub struct Structs;
+
+impl Example for Structs {
+    fn name(&self) -> &'static str {
+        "structs"
+    }
+
+    fn chapter(&self) -> u32 {
+        5
+    }
+
+    fn description(&self) -> &'static str {
+        "defining structs, tuple and unit structs, methods and associated functions"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        structs(out)?;
+        Ok(())
+    }
+}
+
 #[allow(unused)]
-pub fn structs() {
+pub fn structs(out: &mut dyn Write) -> io::Result<()> {
     // creating a struct
     struct User {
         active: bool,
@@ -64,19 +92,20 @@ pub fn structs() {
     fn area(width: u32, height: u32) -> u32 {
         width * height
     }
-    println!("The area of the rectangle is {} square pixels", {
+    writeln!(out, "The area of the rectangle is {} square pixels", {
         area(width1, height1)
-    });
+    })?;
 
     // in the above example, the parameters are not grouped, so we can use a tuple instead
     let rect1: (u32, u32) = (100, 200);
     fn tuple_area(dimensions: (u32, u32)) -> u32 {
         dimensions.0 * dimensions.1
     }
-    println!(
+    writeln!(
+        out,
         "the area of the tuple rectangle is {} squared pixels",
         tuple_area(rect1)
-    );
+    )?;
 
     // refactoring to use a struct instead of a tuple
     #[derive(Debug)] // allows us to print out our struct
@@ -92,10 +121,11 @@ pub fn structs() {
     fn struct_area(rectangle: &Rectangle) -> u32 {
         rectangle.width * rectangle.height
     }
-    println!(
+    writeln!(
+        out,
         "the area of the struct rectangle is {} pixels squared",
         struct_area(&rect2)
-    );
+    )?;
 
     // viewing struct details with debug
     // we use a ref so dbg! does not take ownership of rect2
@@ -108,7 +138,7 @@ pub fn structs() {
         }
     }
     let area: u32 = rect2.area();
-    println!("area using the area method = {area}");
+    writeln!(out, "area using the area method = {area}")?;
 
     // you can name methods and fields the same name
     impl Rectangle {
@@ -121,7 +151,7 @@ pub fn structs() {
         width: 10,
     };
     if zero_width_rect.width() {
-        println!("This rect has a nonzero width!");
+        writeln!(out, "This rect has a nonzero width!")?;
     }
 
     // adding more parameters to methods
@@ -140,7 +170,7 @@ pub fn structs() {
         height: 200,
     };
     let can_hold: bool = big_rect.can_hold(&small_rect);
-    println!("Big rect can hold small rect: {can_hold}");
+    writeln!(out, "Big rect can hold small rect: {can_hold}")?;
 
     // associated methods (method that do not take in &self) are often used to construct a type from a struct
     // associated methods are accessed using "::"
@@ -153,4 +183,5 @@ pub fn structs() {
         }
     }
     let some_square: Rectangle = Rectangle::square(10);
+    Ok(())
 }
@@ -100,6 +100,13 @@ fn greater_than_100() {
     Guess::new(200);
 }
 
+// the same check without a panic, the Guess in guess.rs returns a Result instead
+#[test]
+fn greater_than_100_is_an_error() {
+    let guess = super::guess::Guess::new(200, 1..=100);
+    assert!(guess.unwrap_err().to_string().contains("from 1 to 100"));
+}
+
 // we can also write tests as a result type
 #[test]
 fn it_works() -> Result<(), String> {
@@ -1,13 +1,48 @@
-pub fn variables() {
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+use super::{quiz::Question, Example};
+
+pub struct Variables;
+
+impl Example for Variables {
+    fn name(&self) -> &'static str {
+        "variables"
+    }
+
+    fn chapter(&self) -> u32 {
+        3
+    }
+
+    fn description(&self) -> &'static str {
+        "mutability, constants, shadowing and scope"
+    }
+
+    fn run(&self, out: &mut dyn Write, _input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+        variables(out)?;
+        Ok(())
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+}
+
+pub fn variables(out: &mut dyn Write) -> io::Result<()> {
     // in order to change a variables value, we must mark it with mut
     let mut x = 5;
-    println!("The value of x is: {x}");
+    writeln!(out, "The value of x is: {x}")?;
     x = 6;
-    println!("The value of x is: {x}");
+    writeln!(out, "The value of x is: {x}")?;
 
     // constants cannot have their value changed
     const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
-    println!("How many seconds are in three hours?: {THREE_HOURS_IN_SECONDS}");
+    writeln!(
+        out,
+        "How many seconds are in three hours?: {THREE_HOURS_IN_SECONDS}"
+    )?;
 
     // variable shadowing
     let y = 5;
@@ -16,14 +51,36 @@ pub fn variables() {
     // creating scope
     {
         let y = y * 2;
-        println!("The value of y in the inner scope is: {y}");
+        writeln!(out, "The value of y in the inner scope is: {y}")?;
     }
-    println!("The value of y in the outer scoper is: {y}");
+    writeln!(out, "The value of y in the outer scoper is: {y}")?;
 
     // getting the length of a string
     let spaces = "     ";
     let spaces = spaces.len();
-    println!("How many spaces?: {spaces}");
+    writeln!(out, "How many spaces?: {spaces}")?;
+
+    writeln!(out, "Important note: If you try to mutate a variable into a different type, you will get an error")?;
+    Ok(())
+}
 
-    println!("Important note: If you try to mutate a variable into a different type, you will get an error")
+pub const QUIZ: &[Question] = &[Question {
+    code: r#"
+let y = 5;
+let y = y + 1;
+{
+    let y = y * 2;
+    println!("inner y = {y}");
 }
+println!("outer y = {y}");
+"#,
+    run: |out| {
+        let y = 5;
+        let y = y + 1;
+        {
+            let y = y * 2;
+            writeln!(out, "inner y = {y}")?;
+        }
+        writeln!(out, "outer y = {y}")
+    },
+}];